@@ -0,0 +1,57 @@
+use std::{env, path::Path, process::Command};
+
+/// `sqlx::query!` needs either a live database or the committed `.sqlx` query
+/// data at compile time. Fail early with instructions instead of a wall of
+/// macro errors when neither is available.
+fn main() {
+    println!("cargo:rerun-if-env-changed=DATABASE_URL");
+    println!("cargo:rerun-if-env-changed=SQLX_OFFLINE");
+    println!("cargo:rerun-if-changed=.env");
+    println!("cargo:rerun-if-changed=.sqlx");
+    // Re-embed migrations for `sqlx::migrate!()` whenever one is added
+    println!("cargo:rerun-if-changed=migrations");
+
+    emit_git_commit();
+
+    dotenvy::dotenv().ok();
+
+    let offline = env::var("SQLX_OFFLINE").is_ok_and(|v| v == "true" || v == "1");
+    let has_database = env::var_os("DATABASE_URL").is_some() && !offline;
+    let has_query_data = Path::new(".sqlx")
+        .read_dir()
+        .map(|mut entries| entries.next().is_some())
+        .unwrap_or(false);
+
+    if !has_database && !has_query_data {
+        panic!(
+            "\n\nsolraiser-backend needs query metadata to build.\n\
+             Either set DATABASE_URL to a migrated Postgres database, or build offline\n\
+             with the committed `.sqlx` directory (SQLX_OFFLINE=true).\n\
+             To regenerate `.sqlx` after changing a query:\n\
+             \n    cargo install sqlx-cli --no-default-features --features postgres,rustls\n\
+             \n    sqlx migrate run && cargo sqlx prepare -- --all-targets\n\n"
+        );
+    }
+}
+
+/// Exposes the checked-out commit as `GIT_COMMIT` for `GET /version`. Builds
+/// outside a git checkout (e.g. from a source tarball) simply leave it unset.
+fn emit_git_commit() {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .map(|stdout| stdout.trim().to_string())
+    };
+
+    if let Some(git_dir) = git(&["rev-parse", "--git-dir"]) {
+        println!("cargo:rerun-if-changed={}/HEAD", git_dir);
+        println!("cargo:rerun-if-changed={}/refs/heads", git_dir);
+    }
+    if let Some(commit) = git(&["rev-parse", "--short", "HEAD"]) {
+        println!("cargo:rustc-env=GIT_COMMIT={}", commit);
+    }
+}
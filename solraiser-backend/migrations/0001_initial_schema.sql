@@ -41,4 +41,19 @@ CREATE TABLE IF NOT EXISTS campaign_events (
     indexed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
     FOREIGN KEY (signature) REFERENCES transactions(signature) ON DELETE CASCADE,
     FOREIGN KEY (slot) REFERENCES blocks(slot) ON DELETE CASCADE
-);
\ No newline at end of file
+);
+
+CREATE INDEX IF NOT EXISTS idx_transactions_slot ON transactions(slot);
+CREATE INDEX IF NOT EXISTS idx_transactions_signature ON transactions(signature);
+CREATE INDEX IF NOT EXISTS idx_account_updates_pubkey ON account_updates(pubkey);
+CREATE INDEX IF NOT EXISTS idx_account_updates_slot ON account_updates(slot);
+CREATE INDEX IF NOT EXISTS idx_campaign_events_signature ON campaign_events(signature);
+CREATE INDEX IF NOT EXISTS idx_campaign_events_campaign_id ON campaign_events(campaign_id);
+CREATE INDEX IF NOT EXISTS idx_campaign_events_user_pubkey ON campaign_events(user_pubkey);
+CREATE INDEX IF NOT EXISTS idx_campaign_events_event_type ON campaign_events(event_type);
+CREATE INDEX IF NOT EXISTS idx_campaign_events_slot ON campaign_events(slot);
+
+-- A signature may be delivered more than once (reconnects, backfills), but it
+-- only ever emits one event of a given type per campaign.
+CREATE UNIQUE INDEX IF NOT EXISTS idx_campaign_events_dedup
+    ON campaign_events(signature, event_type, campaign_id);
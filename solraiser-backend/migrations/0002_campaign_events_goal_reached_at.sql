@@ -0,0 +1,2 @@
+-- Set on the donated event that pushed a campaign over its goal
+ALTER TABLE campaign_events ADD COLUMN IF NOT EXISTS goal_reached_at BIGINT;
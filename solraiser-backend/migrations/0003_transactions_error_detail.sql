@@ -0,0 +1,2 @@
+-- Error and program logs of failed transactions, NULL when the transaction succeeded
+ALTER TABLE transactions ADD COLUMN IF NOT EXISTS error_detail JSONB;
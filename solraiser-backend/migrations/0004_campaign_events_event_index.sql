@@ -0,0 +1,9 @@
+-- A transaction can emit several events (batched instructions, CPIs), possibly
+-- more than one of the same type for the same campaign. Each event is keyed
+-- by its position among the transaction's SolRaiser events instead.
+ALTER TABLE campaign_events ADD COLUMN IF NOT EXISTS event_index INTEGER NOT NULL DEFAULT 0;
+
+DROP INDEX IF EXISTS idx_campaign_events_dedup;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_campaign_events_dedup
+    ON campaign_events(signature, event_index);
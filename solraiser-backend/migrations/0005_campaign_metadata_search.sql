@@ -0,0 +1,42 @@
+-- Off-chain metadata (the JSON behind metadata_url), cached by the indexer
+CREATE TABLE IF NOT EXISTS campaign_metadata (
+    campaign_id BIGINT PRIMARY KEY,
+    metadata_url TEXT NOT NULL,
+    name TEXT,
+    description TEXT,
+    image_url TEXT,
+    search_vector TSVECTOR GENERATED ALWAYS AS (
+        to_tsvector('english', COALESCE(name, '') || ' ' || COALESCE(description, ''))
+    ) STORED,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_campaign_metadata_search
+    ON campaign_metadata USING GIN (search_vector);
+
+-- One row per created campaign with its totals reconstructed from events
+CREATE OR REPLACE VIEW campaign_summaries AS
+SELECT
+    c.campaign_id,
+    c.user_pubkey AS creator_pubkey,
+    c.goal_amount,
+    c.deadline,
+    c.metadata_url,
+    COALESCE(d.amount_raised, 0)::BIGINT AS amount_raised,
+    COALESCE(d.donation_count, 0)::BIGINT AS donation_count,
+    COALESCE(w.withdrawn_amount, 0)::BIGINT AS withdrawn_amount,
+    c.slot AS created_slot
+FROM campaign_events c
+LEFT JOIN (
+    SELECT campaign_id, SUM(amount) AS amount_raised, COUNT(*) AS donation_count
+    FROM campaign_events
+    WHERE event_type = 'donated'
+    GROUP BY campaign_id
+) d ON d.campaign_id = c.campaign_id
+LEFT JOIN (
+    SELECT campaign_id, SUM(amount) AS withdrawn_amount
+    FROM campaign_events
+    WHERE event_type = 'withdrawn'
+    GROUP BY campaign_id
+) w ON w.campaign_id = c.campaign_id
+WHERE c.event_type = 'created';
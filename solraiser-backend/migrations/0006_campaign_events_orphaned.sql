@@ -0,0 +1,31 @@
+-- Events whose transaction was dropped by a fork after being indexed
+ALTER TABLE campaign_events ADD COLUMN IF NOT EXISTS orphaned BOOLEAN NOT NULL DEFAULT FALSE;
+
+CREATE INDEX IF NOT EXISTS idx_campaign_events_orphaned ON campaign_events(orphaned)
+    WHERE orphaned;
+
+CREATE OR REPLACE VIEW campaign_summaries AS
+SELECT
+    c.campaign_id,
+    c.user_pubkey AS creator_pubkey,
+    c.goal_amount,
+    c.deadline,
+    c.metadata_url,
+    COALESCE(d.amount_raised, 0)::BIGINT AS amount_raised,
+    COALESCE(d.donation_count, 0)::BIGINT AS donation_count,
+    COALESCE(w.withdrawn_amount, 0)::BIGINT AS withdrawn_amount,
+    c.slot AS created_slot
+FROM campaign_events c
+LEFT JOIN (
+    SELECT campaign_id, SUM(amount) AS amount_raised, COUNT(*) AS donation_count
+    FROM campaign_events
+    WHERE event_type = 'donated' AND NOT orphaned
+    GROUP BY campaign_id
+) d ON d.campaign_id = c.campaign_id
+LEFT JOIN (
+    SELECT campaign_id, SUM(amount) AS withdrawn_amount
+    FROM campaign_events
+    WHERE event_type = 'withdrawn' AND NOT orphaned
+    GROUP BY campaign_id
+) w ON w.campaign_id = c.campaign_id
+WHERE c.event_type = 'created' AND NOT c.orphaned;
@@ -0,0 +1,20 @@
+-- Authoritative campaign state, read straight from the program's accounts.
+-- The event-derived totals in campaign_summaries are checked against this.
+CREATE TABLE IF NOT EXISTS campaigns (
+    pubkey VARCHAR(44) PRIMARY KEY,
+    campaign_id BIGINT NOT NULL,
+    creator_pubkey VARCHAR(44) NOT NULL,
+    goal_amount BIGINT NOT NULL,
+    amount_raised BIGINT NOT NULL,
+    deadline BIGINT NOT NULL,
+    metadata_url TEXT NOT NULL,
+    is_withdrawn BOOLEAN NOT NULL,
+    withdrawn_amount BIGINT NOT NULL,
+    goal_reached_at BIGINT NOT NULL,
+    decimals SMALLINT NOT NULL,
+    symbol VARCHAR(8),
+    snapshot_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_campaigns_campaign_id ON campaigns(campaign_id);
+CREATE INDEX IF NOT EXISTS idx_campaigns_creator_pubkey ON campaigns(creator_pubkey);
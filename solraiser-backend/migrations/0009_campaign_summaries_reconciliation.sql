@@ -0,0 +1,33 @@
+-- Compare each campaign's event-derived amount_raised with the on-chain
+-- snapshot. Both columns stay NULL until the campaign has been snapshotted.
+CREATE OR REPLACE VIEW campaign_summaries AS
+SELECT
+    c.campaign_id,
+    c.user_pubkey AS creator_pubkey,
+    c.goal_amount,
+    c.deadline,
+    c.metadata_url,
+    COALESCE(d.amount_raised, 0)::BIGINT AS amount_raised,
+    COALESCE(d.donation_count, 0)::BIGINT AS donation_count,
+    COALESCE(w.withdrawn_amount, 0)::BIGINT AS withdrawn_amount,
+    c.slot AS created_slot,
+    c.decimals,
+    c.symbol,
+    (snap.amount_raised = COALESCE(d.amount_raised, 0)) AS reconciled,
+    (snap.amount_raised - COALESCE(d.amount_raised, 0))::BIGINT AS discrepancy_lamports
+FROM campaign_events c
+LEFT JOIN (
+    SELECT campaign_id, SUM(amount) AS amount_raised, COUNT(*) AS donation_count
+    FROM campaign_events
+    WHERE event_type = 'donated' AND NOT orphaned
+    GROUP BY campaign_id
+) d ON d.campaign_id = c.campaign_id
+LEFT JOIN (
+    SELECT campaign_id, SUM(amount) AS withdrawn_amount
+    FROM campaign_events
+    WHERE event_type = 'withdrawn' AND NOT orphaned
+    GROUP BY campaign_id
+) w ON w.campaign_id = c.campaign_id
+LEFT JOIN campaigns snap
+    ON snap.campaign_id = c.campaign_id AND snap.creator_pubkey = c.user_pubkey
+WHERE c.event_type = 'created' AND NOT c.orphaned;
@@ -0,0 +1,3 @@
+-- Log lines of the instruction that emitted each event, so a suspicious event
+-- can be checked without re-fetching its transaction; NULL for older rows
+ALTER TABLE campaign_events ADD COLUMN IF NOT EXISTS raw_logs TEXT[];
@@ -0,0 +1,10 @@
+-- Lifecycle status of each campaign, kept current by the expiry sweeper so
+-- readers share one view of which campaigns have just expired
+CREATE TABLE IF NOT EXISTS campaign_statuses (
+    campaign_id BIGINT PRIMARY KEY,
+    status VARCHAR(10) NOT NULL
+        CHECK (status IN ('active', 'succeeded', 'failed', 'withdrawn')),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS idx_campaign_statuses_status ON campaign_statuses(status);
@@ -0,0 +1,3 @@
+-- Campaign total right after a donated event, as reported by the program.
+-- NULL for events emitted before the program included it.
+ALTER TABLE campaign_events ADD COLUMN IF NOT EXISTS amount_raised BIGINT;
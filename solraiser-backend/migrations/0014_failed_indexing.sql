@@ -0,0 +1,9 @@
+-- Transactions the indexer gave up on after retrying. Reprocess one with
+-- `solraiser-backend reindex <signature>`, which removes its row on success.
+CREATE TABLE IF NOT EXISTS failed_indexing (
+    signature VARCHAR(88) PRIMARY KEY,
+    slot BIGINT NOT NULL,
+    attempts INTEGER NOT NULL,
+    last_error TEXT NOT NULL,
+    failed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,47 @@
+-- Goal progress computed once, the same way as the program's
+-- Campaign::progress_bps: basis points rounded down, capped at 10000 once the
+-- goal is reached. NUMERIC keeps amount_raised * 10000 from overflowing BIGINT.
+CREATE OR REPLACE VIEW campaign_summaries AS
+SELECT
+    c.campaign_id,
+    c.user_pubkey AS creator_pubkey,
+    c.goal_amount,
+    c.deadline,
+    c.metadata_url,
+    COALESCE(d.amount_raised, 0)::BIGINT AS amount_raised,
+    COALESCE(d.donation_count, 0)::BIGINT AS donation_count,
+    COALESCE(w.withdrawn_amount, 0)::BIGINT AS withdrawn_amount,
+    c.slot AS created_slot,
+    c.decimals,
+    c.symbol,
+    (snap.amount_raised = COALESCE(d.amount_raised, 0)) AS reconciled,
+    (snap.amount_raised - COALESCE(d.amount_raised, 0))::BIGINT AS discrepancy_lamports,
+    COALESCE(u.last_event_slot, c.slot)::BIGINT AS last_event_slot,
+    to_timestamp(u.last_block_time) AS updated_at,
+    CASE WHEN COALESCE(d.amount_raised, 0) >= COALESCE(c.goal_amount, 0) THEN 10000
+         ELSE FLOOR(COALESCE(d.amount_raised, 0)::NUMERIC * 10000 / c.goal_amount)::INT
+    END AS progress_bps,
+    COALESCE(d.amount_raised, 0) >= COALESCE(c.goal_amount, 0) AS is_goal_reached
+FROM campaign_events c
+LEFT JOIN (
+    SELECT campaign_id, SUM(amount) AS amount_raised, COUNT(*) AS donation_count
+    FROM campaign_events
+    WHERE event_type = 'donated' AND NOT orphaned
+    GROUP BY campaign_id
+) d ON d.campaign_id = c.campaign_id
+LEFT JOIN (
+    SELECT campaign_id, SUM(amount) AS withdrawn_amount
+    FROM campaign_events
+    WHERE event_type = 'withdrawn' AND NOT orphaned
+    GROUP BY campaign_id
+) w ON w.campaign_id = c.campaign_id
+LEFT JOIN (
+    SELECT e.campaign_id, MAX(e.slot) AS last_event_slot, MAX(b.block_time) AS last_block_time
+    FROM campaign_events e
+    LEFT JOIN blocks b ON b.slot = e.slot
+    WHERE NOT e.orphaned
+    GROUP BY e.campaign_id
+) u ON u.campaign_id = c.campaign_id
+LEFT JOIN campaigns snap
+    ON snap.campaign_id = c.campaign_id AND snap.creator_pubkey = c.user_pubkey
+WHERE c.event_type = 'created' AND NOT c.orphaned;
@@ -0,0 +1,3 @@
+-- Consecutive reorg checks that found no status for the event's transaction;
+-- the event is only flagged orphaned once this reaches the reconciler's threshold
+ALTER TABLE campaign_events ADD COLUMN IF NOT EXISTS status_misses SMALLINT NOT NULL DEFAULT 0;
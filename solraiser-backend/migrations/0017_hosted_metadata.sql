@@ -0,0 +1,9 @@
+-- Metadata documents built by build-create-tx and served at /v1/metadata/:id,
+-- for creators who don't host their own
+CREATE TABLE IF NOT EXISTS hosted_metadata (
+    id TEXT PRIMARY KEY,
+    name TEXT NOT NULL,
+    description TEXT NOT NULL,
+    image_url TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,161 @@
+//! Off-chain decoding of SolRaiser program accounts, mirroring their on-chain
+//! layouts field for field.
+
+use anyhow::{Context, Result};
+use borsh::BorshDeserialize;
+use solana_sdk::{hash::hashv, pubkey::Pubkey};
+
+use crate::instructions::SYMBOL_LEN;
+
+/// Allocated size of a `Campaign` account, the program's `Campaign::LEN`
+pub const CAMPAIGN_ACCOUNT_LEN: usize = 375;
+/// Allocated size of a `CreatorStats` account, the program's `CreatorStats::LEN`
+pub const CREATOR_STATS_ACCOUNT_LEN: usize = 13;
+/// Lamports a goal must exceed the campaign's rent by, the program's `MIN_GOAL_ABOVE_RENT`
+pub const MIN_GOAL_ABOVE_RENT: u64 = 1_000_000;
+/// Progress of a campaign at or past its goal, the program's `FULL_PROGRESS_BPS`
+pub const FULL_PROGRESS_BPS: u16 = 10_000;
+
+/// The program's `Campaign::progress_bps`: share of the goal raised in basis
+/// points, capped at [`FULL_PROGRESS_BPS`], computed in u128 so it can't overflow
+pub fn progress_bps(amount_raised: u64, goal_amount: u64) -> u16 {
+    if amount_raised >= goal_amount {
+        return FULL_PROGRESS_BPS;
+    }
+    (amount_raised as u128 * FULL_PROGRESS_BPS as u128 / goal_amount as u128) as u16
+}
+
+/// The program's `Campaign::min_goal_amount`, given the rent-exempt minimum of
+/// a campaign account
+pub fn min_goal_amount(campaign_rent: u64) -> u64 {
+    campaign_rent.saturating_add(MIN_GOAL_ABOVE_RENT)
+}
+
+/// First 8 bytes of `sha256("account:<name>")`, Anchor's account discriminator
+pub fn account_discriminator(name: &str) -> [u8; 8] {
+    let hash = hashv(&[b"account:", name.as_bytes()]);
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash.to_bytes()[..8]);
+    discriminator
+}
+
+/// Mirrors the program's `Campaign` account
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize)]
+pub struct CampaignAccount {
+    pub creator_pubkey: Pubkey,
+    pub campaign_id: u64,
+    pub goal_amount: u64,
+    pub amount_raised: u64,
+    pub deadline: i64,
+    pub metadata_url: String,
+    pub is_withdrawn: bool,
+    pub withdrawn_amount: u64,
+    pub goal_reached_at: i64,
+    pub withdraw_cooldown_secs: u64,
+    pub last_withdraw_at: i64,
+    pub decimals: u8,
+    pub symbol: [u8; SYMBOL_LEN],
+    /// Payouts are split between the campaign's beneficiaries
+    pub has_beneficiaries: bool,
+}
+
+/// Checks the discriminator of account `name` and decodes the fields after it.
+/// Accounts are allocated at their maximum size, so unused trailing bytes (e.g.
+/// the tail of a short `metadata_url`) are ignored rather than rejected.
+fn decode_account<T: BorshDeserialize>(name: &str, data: &[u8]) -> Result<T> {
+    anyhow::ensure!(
+        data.len() >= 8 && data[..8] == account_discriminator(name),
+        "Not a {} account",
+        name
+    );
+
+    let mut body = &data[8..];
+    T::deserialize(&mut body).with_context(|| format!("Failed to decode {} account", name))
+}
+
+impl CampaignAccount {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        decode_account("Campaign", data)
+    }
+
+    pub fn is_goal_reached(&self) -> bool {
+        self.amount_raised >= self.goal_amount
+    }
+
+    pub fn progress_bps(&self) -> u16 {
+        progress_bps(self.amount_raised, self.goal_amount)
+    }
+}
+
+/// Mirrors the program's `Registry` account
+#[derive(Debug, Clone, PartialEq, Eq, BorshDeserialize)]
+pub struct RegistryAccount {
+    /// Id the next created campaign will be assigned
+    pub next_id: u64,
+    pub bump: u8,
+    pub admin: Pubkey,
+    /// Open campaigns allowed per creator; 0 means no cap
+    pub max_campaigns_per_creator: u32,
+}
+
+impl RegistryAccount {
+    pub fn decode(data: &[u8]) -> Result<Self> {
+        decode_account("Registry", data)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn campaign_discriminator_matches_idl() {
+        assert_eq!(
+            account_discriminator("Campaign"),
+            [50, 40, 49, 11, 157, 220, 229, 192]
+        );
+    }
+
+    #[test]
+    fn progress_bps_rounds_down() {
+        assert_eq!(progress_bps(0, 3_000), 0);
+        assert_eq!(progress_bps(1, 3_000), 3);
+        assert_eq!(progress_bps(2_999, 3_000), 9_996);
+    }
+
+    #[test]
+    fn progress_bps_does_not_overflow_at_large_amounts() {
+        // amount_raised * 10_000 is far beyond u64 here
+        assert_eq!(progress_bps(u64::MAX / 2, u64::MAX), 4_999);
+        assert_eq!(progress_bps(u64::MAX - 1, u64::MAX), 9_999);
+        assert_eq!(progress_bps(10_000_000 * 1_000_000_000, u64::MAX), 5);
+    }
+
+    #[test]
+    fn progress_bps_caps_overfunded_campaigns() {
+        assert_eq!(progress_bps(3_000, 3_000), FULL_PROGRESS_BPS);
+        assert_eq!(progress_bps(u64::MAX, 1), FULL_PROGRESS_BPS);
+        assert_eq!(progress_bps(0, 0), FULL_PROGRESS_BPS);
+    }
+
+    #[test]
+    fn registry_decodes_next_id_and_settings() {
+        let admin = Pubkey::new_unique();
+        let mut data = account_discriminator("Registry").to_vec();
+        data.extend(42u64.to_le_bytes());
+        data.push(254);
+        data.extend(admin.to_bytes());
+        data.extend(5u32.to_le_bytes());
+
+        assert_eq!(
+            RegistryAccount::decode(&data).unwrap(),
+            RegistryAccount {
+                next_id: 42,
+                bump: 254,
+                admin,
+                max_campaigns_per_creator: 5,
+            }
+        );
+        assert!(CampaignAccount::decode(&data).is_err());
+    }
+}
@@ -0,0 +1,141 @@
+//! Command line of the backend binary: the API server, plus one-shot repair
+//! commands that run against the same database and RPC without serving.
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use solana_sdk::pubkey::Pubkey;
+
+use crate::{accounts::CampaignAccount, models::CampaignSummary, pda, state::AppState};
+
+#[derive(Debug, Parser)]
+#[command(
+    name = "solraiser-backend",
+    version,
+    about = "SolRaiser indexer and API"
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Subcommand)]
+pub enum Command {
+    /// Run the indexer and the HTTP API (the default)
+    Serve,
+    /// Fetch, parse and store a single transaction, e.g. one listed in `failed_indexing`
+    Reindex { signature: String },
+    /// Compare a campaign's indexed totals against its on-chain account
+    Verify { campaign_id: i64 },
+}
+
+impl Cli {
+    /// The subcommand to run; `serve` when none is given, as before subcommands existed
+    pub fn command(self) -> Command {
+        self.command.unwrap_or(Command::Serve)
+    }
+}
+
+/// Prints a campaign's event-derived totals next to its on-chain account.
+/// Returns whether they match.
+pub async fn verify_campaign(state: &AppState, campaign_id: i64) -> Result<bool> {
+    let summary = sqlx::query_as::<_, CampaignSummary>(
+        "SELECT * FROM campaign_summaries WHERE campaign_id = $1",
+    )
+    .bind(campaign_id)
+    .fetch_optional(&state.db)
+    .await
+    .context("Failed to load campaign summary")?
+    .with_context(|| format!("Campaign {} has not been indexed", campaign_id))?;
+
+    let creator: Pubkey = summary
+        .creator_pubkey
+        .parse()
+        .context("Indexed creator is not a pubkey")?;
+    let (campaign_pda, _) =
+        pda::derive_campaign_pda(&state.program_id, &creator, campaign_id as u64);
+
+    let rpc_client = state.rpc_client.clone();
+    let data = tokio::task::spawn_blocking(move || rpc_client.get_account_data(&campaign_pda))
+        .await
+        .context("Failed to spawn campaign account lookup")?
+        .with_context(|| format!("Failed to fetch campaign account {}", campaign_pda))?;
+    let account = CampaignAccount::decode(&data)?;
+
+    println!("Campaign {} ({})", campaign_id, campaign_pda);
+    println!(
+        "{:<18} {:>20} {:>20} {:>20}",
+        "", "indexed", "on-chain", "on-chain - indexed"
+    );
+    let mut matches = true;
+    for (field, indexed, on_chain) in [
+        (
+            "amount_raised",
+            summary.amount_raised,
+            account.amount_raised as i64,
+        ),
+        (
+            "withdrawn_amount",
+            summary.withdrawn_amount,
+            account.withdrawn_amount as i64,
+        ),
+        (
+            "progress_bps",
+            summary.progress_bps as i64,
+            account.progress_bps() as i64,
+        ),
+    ] {
+        println!(
+            "{:<18} {:>20} {:>20} {:>20}",
+            field,
+            indexed,
+            on_chain,
+            on_chain - indexed
+        );
+        matches &= indexed == on_chain;
+    }
+
+    println!(
+        "{}",
+        if matches {
+            "OK: indexed totals match the chain"
+        } else {
+            "MISMATCH: indexed totals differ from the chain"
+        }
+    );
+    Ok(matches)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Command {
+        Cli::try_parse_from(args).unwrap().command()
+    }
+
+    #[test]
+    fn no_subcommand_serves() {
+        assert_eq!(parse(&["solraiser-backend"]), Command::Serve);
+        assert_eq!(parse(&["solraiser-backend", "serve"]), Command::Serve);
+    }
+
+    #[test]
+    fn reindex_takes_a_signature() {
+        assert_eq!(
+            parse(&["solraiser-backend", "reindex", "5sig"]),
+            Command::Reindex {
+                signature: "5sig".to_string()
+            }
+        );
+        assert!(Cli::try_parse_from(["solraiser-backend", "reindex"]).is_err());
+    }
+
+    #[test]
+    fn verify_takes_a_campaign_id() {
+        assert_eq!(
+            parse(&["solraiser-backend", "verify", "42"]),
+            Command::Verify { campaign_id: 42 }
+        );
+        assert!(Cli::try_parse_from(["solraiser-backend", "verify", "forty-two"]).is_err());
+    }
+}
@@ -5,25 +5,149 @@ const DEFAULT_SOLANA_RPC: &str = "https://api.mainnet-beta.solana.com";
 const DEFAULT_SERVER_HOST: &str = "0.0.0.0";
 const DEFAULT_SERVER_PORT: u16 = 5000;
 const DEFAULT_PROGRAM_ID: &str = "62NbBCCxPfR83xtgw3AaxKGHyyDdxobrcCGzA7s7LFie";
+const DEFAULT_REORG_WINDOW_SLOTS: u64 = 300;
+const DEFAULT_REORG_CHECK_INTERVAL_SECS: u64 = 30;
+const DEFAULT_SNAPSHOT_INTERVAL_SECS: u64 = 300;
+const DEFAULT_EXPIRY_SWEEP_INTERVAL_SECS: u64 = 60;
+const DEFAULT_CHANNEL_BUFFER_SIZE: usize = 1000;
+const DEFAULT_RPC_PROXY_RATE_LIMIT: u32 = crate::rate_limit::DEFAULT_RATE_LIMIT;
+const DEFAULT_ALLOWED_ORIGINS: &str = "*";
+const DEFAULT_INDEX_EVENT_TYPES: &str = "created,donated,withdrawn";
+
+/// Origins the API accepts cross-origin requests from
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub enum AllowedOrigins {
+    /// `*`: any origin, with any method and header
+    Any,
+    List(Vec<String>),
+}
+
+impl AllowedOrigins {
+    /// Parses `ALLOWED_ORIGINS`: `*`, or a comma-separated list of origins
+    pub fn parse(value: &str) -> Result<Self, anyhow::Error> {
+        if value.trim() == "*" {
+            return Ok(AllowedOrigins::Any);
+        }
+
+        let origins: Vec<String> = value
+            .split(',')
+            .map(|origin| origin.trim().trim_end_matches('/').to_string())
+            .filter(|origin| !origin.is_empty())
+            .collect();
+
+        anyhow::ensure!(
+            !origins.is_empty(),
+            "ALLOWED_ORIGINS must be '*' or a comma-separated list of origins"
+        );
+        for origin in &origins {
+            anyhow::ensure!(
+                origin.starts_with("https://") || origin.starts_with("http://"),
+                "ALLOWED_ORIGINS entry '{}' must start with http:// or https://",
+                origin
+            );
+        }
+
+        Ok(AllowedOrigins::List(origins))
+    }
+}
+
+/// Campaign event types the indexer writes to `campaign_events`. Blocks and
+/// transactions are stored either way.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub struct IndexEventTypes {
+    pub created: bool,
+    pub donated: bool,
+    pub withdrawn: bool,
+}
+
+impl Default for IndexEventTypes {
+    fn default() -> Self {
+        IndexEventTypes {
+            created: true,
+            donated: true,
+            withdrawn: true,
+        }
+    }
+}
+
+impl IndexEventTypes {
+    /// Parses `INDEX_EVENT_TYPES`: a comma-separated subset of `created,donated,withdrawn`
+    pub fn parse(value: &str) -> Result<Self, anyhow::Error> {
+        let mut types = IndexEventTypes {
+            created: false,
+            donated: false,
+            withdrawn: false,
+        };
+
+        for event_type in value.split(',').map(str::trim).filter(|t| !t.is_empty()) {
+            match event_type {
+                "created" => types.created = true,
+                "donated" => types.donated = true,
+                "withdrawn" => types.withdrawn = true,
+                other => anyhow::bail!(
+                    "INDEX_EVENT_TYPES entry '{}' must be one of created, donated, withdrawn",
+                    other
+                ),
+            }
+        }
+
+        anyhow::ensure!(
+            types.created || types.donated || types.withdrawn,
+            "INDEX_EVENT_TYPES must list at least one of created, donated, withdrawn"
+        );
+        Ok(types)
+    }
+
+    /// Whether events stored as `event_type` should be indexed
+    pub fn includes(&self, event_type: &str) -> bool {
+        match event_type {
+            "created" => self.created,
+            "donated" => self.donated,
+            "withdrawn" => self.withdrawn,
+            _ => false,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Deserialize)]
 pub struct Config {
     pub database_url: String,
     pub solana_rpc_url: String,
+    /// Websocket endpoint for log subscriptions; derived from the RPC URL if unset
+    pub solana_ws_url: String,
     pub server_host: String,
     pub server_port: u16,
     pub program_id: String,
     pub start_slot: Option<u64>,
+    /// How far behind the last indexed slot events are re-checked for forks
+    pub reorg_window_slots: u64,
+    pub reorg_check_interval_secs: u64,
+    /// How often campaign accounts are re-read from chain into `campaigns`
+    pub snapshot_interval_secs: u64,
+    /// How often `campaign_statuses` is brought up to date with deadlines
+    pub expiry_sweep_interval_secs: u64,
+    /// Log messages the indexer queues between ingestion and processing
+    pub channel_buffer_size: usize,
+    pub allowed_origins: AllowedOrigins,
+    pub index_event_types: IndexEventTypes,
+    /// Requests per minute each client IP may send through `/rpc`
+    pub rpc_proxy_rate_limit: u32,
+    /// Base URL clients reach this API at, e.g. `https://api.solraiser.app`
+    pub public_url: Option<String>,
 }
 
 impl Config {
     pub fn from_env() -> Result<Self, anyhow::Error> {
         dotenvy::dotenv().ok();
 
+        let solana_rpc_url =
+            std::env::var("SOLANA_RPC_URL").unwrap_or_else(|_| DEFAULT_SOLANA_RPC.to_string());
+
         Ok(Config {
             database_url: std::env::var("DATABASE_URL")?,
-            solana_rpc_url: std::env::var("SOLANA_RPC_URL")
-                .unwrap_or_else(|_| DEFAULT_SOLANA_RPC.to_string()),
+            solana_ws_url: std::env::var("SOLANA_WS_URL")
+                .unwrap_or_else(|_| ws_url_for(&solana_rpc_url)),
+            solana_rpc_url,
             server_host: std::env::var("SERVER_HOST")
                 .unwrap_or_else(|_| DEFAULT_SERVER_HOST.to_string()),
             server_port: std::env::var("SERVER_PORT")
@@ -35,6 +159,121 @@ impl Config {
             start_slot: std::env::var("START_SLOT")
                 .ok()
                 .and_then(|s| s.parse().ok()),
+            reorg_window_slots: std::env::var("REORG_WINDOW_SLOTS")
+                .unwrap_or_else(|_| DEFAULT_REORG_WINDOW_SLOTS.to_string())
+                .parse::<u64>()
+                .context("REORG_WINDOW_SLOTS must be a number of slots")?,
+            reorg_check_interval_secs: std::env::var("REORG_CHECK_INTERVAL_SECS")
+                .unwrap_or_else(|_| DEFAULT_REORG_CHECK_INTERVAL_SECS.to_string())
+                .parse::<u64>()
+                .ok()
+                .filter(|&secs| secs > 0)
+                .context("REORG_CHECK_INTERVAL_SECS must be a positive number of seconds")?,
+            snapshot_interval_secs: std::env::var("SNAPSHOT_INTERVAL_SECS")
+                .unwrap_or_else(|_| DEFAULT_SNAPSHOT_INTERVAL_SECS.to_string())
+                .parse::<u64>()
+                .ok()
+                .filter(|&secs| secs > 0)
+                .context("SNAPSHOT_INTERVAL_SECS must be a positive number of seconds")?,
+            expiry_sweep_interval_secs: std::env::var("EXPIRY_SWEEP_INTERVAL_SECS")
+                .unwrap_or_else(|_| DEFAULT_EXPIRY_SWEEP_INTERVAL_SECS.to_string())
+                .parse::<u64>()
+                .ok()
+                .filter(|&secs| secs > 0)
+                .context("EXPIRY_SWEEP_INTERVAL_SECS must be a positive number of seconds")?,
+            channel_buffer_size: std::env::var("CHANNEL_BUFFER_SIZE")
+                .unwrap_or_else(|_| DEFAULT_CHANNEL_BUFFER_SIZE.to_string())
+                .parse::<usize>()
+                .ok()
+                .filter(|&size| size > 0)
+                .context("CHANNEL_BUFFER_SIZE must be a positive number of messages")?,
+            allowed_origins: AllowedOrigins::parse(
+                &std::env::var("ALLOWED_ORIGINS")
+                    .unwrap_or_else(|_| DEFAULT_ALLOWED_ORIGINS.to_string()),
+            )?,
+            index_event_types: IndexEventTypes::parse(
+                &std::env::var("INDEX_EVENT_TYPES")
+                    .unwrap_or_else(|_| DEFAULT_INDEX_EVENT_TYPES.to_string()),
+            )?,
+            rpc_proxy_rate_limit: std::env::var("RPC_PROXY_RATE_LIMIT")
+                .unwrap_or_else(|_| DEFAULT_RPC_PROXY_RATE_LIMIT.to_string())
+                .parse::<u32>()
+                .ok()
+                .filter(|&limit| limit > 0)
+                .context("RPC_PROXY_RATE_LIMIT must be a positive number of requests per minute")?,
+            public_url: std::env::var("PUBLIC_URL")
+                .ok()
+                .map(|url| url.trim_end_matches('/').to_string())
+                .filter(|url| !url.is_empty()),
         })
     }
 }
+
+/// `https://host` -> `wss://host`, `http://host` -> `ws://host`
+fn ws_url_for(rpc_url: &str) -> String {
+    if let Some(rest) = rpc_url.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = rpc_url.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        rpc_url.to_string()
+    }
+}
+
+/// Cluster name for an RPC URL, or "custom" for unrecognised endpoints
+pub fn network_for(rpc_url: &str) -> &'static str {
+    if rpc_url.contains("devnet") {
+        "devnet"
+    } else if rpc_url.contains("testnet") {
+        "testnet"
+    } else if rpc_url.contains("mainnet") {
+        "mainnet-beta"
+    } else if rpc_url.contains("localhost") || rpc_url.contains("127.0.0.1") {
+        "localnet"
+    } else {
+        "custom"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn allowed_origins_parses_wildcard_and_lists() {
+        assert_eq!(AllowedOrigins::parse("*").unwrap(), AllowedOrigins::Any);
+        assert_eq!(
+            AllowedOrigins::parse("https://solraiser.app, http://localhost:3000/,").unwrap(),
+            AllowedOrigins::List(vec![
+                "https://solraiser.app".to_string(),
+                "http://localhost:3000".to_string(),
+            ])
+        );
+        assert!(AllowedOrigins::parse("").is_err());
+        assert!(AllowedOrigins::parse("solraiser.app").is_err());
+    }
+
+    #[test]
+    fn index_event_types_parses_subsets() {
+        assert_eq!(
+            IndexEventTypes::parse(DEFAULT_INDEX_EVENT_TYPES).unwrap(),
+            IndexEventTypes::default()
+        );
+
+        let donations_only = IndexEventTypes::parse(" donated ,").unwrap();
+        assert!(donations_only.includes("donated"));
+        assert!(!donations_only.includes("created"));
+        assert!(!donations_only.includes("withdrawn"));
+
+        assert!(IndexEventTypes::parse("").is_err());
+        assert!(IndexEventTypes::parse("donated,refunded").is_err());
+    }
+
+    #[test]
+    fn network_is_derived_from_rpc_url() {
+        assert_eq!(network_for(DEFAULT_SOLANA_RPC), "mainnet-beta");
+        assert_eq!(network_for("https://api.devnet.solana.com"), "devnet");
+        assert_eq!(network_for("http://127.0.0.1:8899"), "localnet");
+        assert_eq!(network_for("https://rpc.example.com"), "custom");
+    }
+}
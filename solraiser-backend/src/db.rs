@@ -0,0 +1,45 @@
+use std::collections::HashSet;
+
+use anyhow::{Context, Result};
+use sqlx::{
+    migrate::{Migrate, Migrator},
+    PgPool,
+};
+use tracing::info;
+
+/// Migrations embedded from `./migrations` at compile time
+pub static MIGRATOR: Migrator = sqlx::migrate!();
+
+/// Brings the schema up to date, logging every migration applied on this run.
+/// Already-applied migrations are skipped, so this is safe to call on every boot.
+pub async fn run_migrations(pool: &PgPool) -> Result<()> {
+    let applied: HashSet<i64> = {
+        let mut conn = pool
+            .acquire()
+            .await
+            .context("Failed to acquire connection for migrations")?;
+        conn.ensure_migrations_table()
+            .await
+            .context("Failed to create migrations table")?;
+        conn.list_applied_migrations()
+            .await
+            .context("Failed to list applied migrations")?
+            .into_iter()
+            .map(|m| m.version)
+            .collect()
+    };
+
+    MIGRATOR
+        .run(pool)
+        .await
+        .context("Failed to run database migrations")?;
+
+    for migration in MIGRATOR.iter().filter(|m| !applied.contains(&m.version)) {
+        info!(
+            "Applied migration {}: {}",
+            migration.version, migration.description
+        );
+    }
+
+    Ok(())
+}
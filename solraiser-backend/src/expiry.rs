@@ -0,0 +1,65 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+use tokio::task::JoinHandle;
+use tracing::{error, info};
+
+use crate::state::AppState;
+
+/// Recomputes `campaign_statuses` from `campaign_summaries` as of `now` (unix
+/// seconds): `withdrawn` once the campaign is fully withdrawn, else `active`
+/// until the deadline, then `succeeded` or `failed` depending on whether the
+/// goal was met. A campaign counts as fully withdrawn when its snapshot says
+/// `is_withdrawn`, or when its withdrawal events add up to everything raised;
+/// partial withdrawals leave it `succeeded`. Returns the number of campaigns
+/// whose status was added or changed.
+pub async fn sweep_campaign_statuses(db: &PgPool, now: i64) -> Result<u64> {
+    let changed = sqlx::query!(
+        r#"
+        INSERT INTO campaign_statuses (campaign_id, status)
+        SELECT s.campaign_id,
+               CASE
+                   WHEN COALESCE(c.is_withdrawn, FALSE)
+                        OR (s.withdrawn_amount > 0 AND s.withdrawn_amount >= s.amount_raised)
+                       THEN 'withdrawn'
+                   WHEN s.deadline IS NULL OR s.deadline > $1 THEN 'active'
+                   WHEN s.amount_raised >= COALESCE(s.goal_amount, 0) THEN 'succeeded'
+                   ELSE 'failed'
+               END
+        FROM campaign_summaries s
+        LEFT JOIN campaigns c ON c.campaign_id = s.campaign_id
+        ON CONFLICT (campaign_id) DO UPDATE SET
+            status = EXCLUDED.status,
+            updated_at = CURRENT_TIMESTAMP
+        WHERE campaign_statuses.status <> EXCLUDED.status
+        "#,
+        now,
+    )
+    .execute(db)
+    .await
+    .context("Failed to sweep campaign statuses")?
+    .rows_affected();
+
+    if changed > 0 {
+        info!("Updated the status of {} campaigns", changed);
+    }
+    Ok(changed)
+}
+
+/// Runs [`sweep_campaign_statuses`] every `interval`
+pub fn spawn_expiry_sweeper(state: Arc<AppState>, interval: Duration) -> JoinHandle<()> {
+    info!("Campaign expiry sweeper running every {:?}", interval);
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            let now = chrono::Utc::now().timestamp();
+            if let Err(e) = sweep_campaign_statuses(&state.db, now).await {
+                error!("Campaign expiry sweep error: {:?}", e);
+            }
+        }
+    })
+}
+
@@ -1,15 +1,278 @@
+use anyhow::Context;
 use axum::{
-    extract::{Path, Query},
-    http::StatusCode,
+    body::Body,
+    extract::{Path, Query, State},
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
     Json,
 };
+use base64::Engine;
+use futures::TryStreamExt;
 use serde::{Deserialize, Serialize};
-use std::sync::Arc;
+use solana_client::{
+    rpc_client::RpcClient,
+    rpc_config::{CommitmentConfig, RpcTransactionConfig},
+    rpc_request::RpcRequest,
+};
+use solana_sdk::{
+    pubkey::Pubkey, signature::Signature, transaction::Transaction as SolanaTransaction,
+};
+use solana_transaction_status::{
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+    UiTransactionEncoding,
+};
+use tracing::error;
+
+use crate::{
+    accounts::{self, RegistryAccount, CAMPAIGN_ACCOUNT_LEN, CREATOR_STATS_ACCOUNT_LEN},
+    config,
+    error::AppError,
+    indexer::{CampaignEvent, SolanaIndexer, ANONYMOUS_DONOR},
+    instructions, metadata,
+    models::{
+        Block, CampaignCreateRequest, CampaignCreateTransaction, CampaignDetail,
+        CampaignEventRecord, CampaignListing, CampaignMetadata, CampaignSearchResult,
+        CampaignSummary, DonorDonation, DonorHistory, EndingSoonCampaign, EventCursor, EventPage,
+        FeeEstimate, ProgressPoint, RefundStatus, TopDonor, Transaction,
+    },
+    pda,
+    state::AppState,
+};
+
+const DEFAULT_EVENTS_LIMIT: i64 = 100;
+const MAX_EVENTS_LIMIT: i64 = 1000;
+const DEFAULT_SEARCH_LIMIT: i64 = 20;
+const MAX_SEARCH_LIMIT: i64 = 100;
+const DEFAULT_CAMPAIGNS_LIMIT: i64 = 20;
+const MAX_CAMPAIGNS_LIMIT: i64 = 100;
+/// Widest `/campaigns/ending-soon` window, a year; keeps `within_hours * 3600` in range
+const MAX_ENDING_SOON_HOURS: i64 = 24 * 366;
+/// Rows `/campaigns/stream` reads ahead of a slow client
+const CAMPAIGN_STREAM_BUFFER: usize = 64;
+const DEFAULT_BLOCKS_LIMIT: i64 = 100;
+const MAX_BLOCKS_LIMIT: i64 = 1000;
+const DEFAULT_TOP_DONORS_LIMIT: i64 = 10;
+const MAX_TOP_DONORS_LIMIT: i64 = 100;
+const DEFAULT_DETAIL_EVENTS: i64 = 10;
+const MAX_DETAIL_EVENTS: i64 = 100;
+/// Read-only RPC methods `/rpc` forwards; none of them scan the chain
+const PROXIED_RPC_METHODS: &[&str] = &[
+    "getAccountInfo",
+    "getBalance",
+    "getLatestBlockhash",
+    "getMinimumBalanceForRentExemption",
+    "getSignatureStatuses",
+    "getSignaturesForAddress",
+    "getSlot",
+    "getTransaction",
+];
+/// Every allowlisted method takes at most an argument and a config object
+const MAX_PROXIED_RPC_PARAMS: usize = 2;
+/// Request body limit of `/rpc`; an allowlisted call is far smaller
+pub const MAX_RPC_PROXY_BODY_BYTES: usize = 16 * 1024;
+/// Campaigns are denominated in lamports unless the creator says otherwise
+const DEFAULT_DECIMALS: u8 = 9;
+const DEFAULT_SYMBOL: &str = "SOL";
+
+pub async fn root() -> &'static str {
+    "SolRaiser Backend API v1.0"
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HealthRespone {
+    pub status: String,
+    pub timestamp: u64,
+}
+
+pub async fn health_check() -> Result<Json<HealthRespone>, AppError> {
+    Ok(Json(HealthRespone {
+        status: "healthy".to_string(),
+        timestamp: std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_err(|e| AppError::InternalServerError(e.to_string()))?
+            .as_secs(),
+    }))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct VersionResponse {
+    pub version: String,
+    pub program_id: String,
+    pub network: String,
+    /// Commit the binary was built from, when built inside a git checkout
+    pub git_commit: Option<String>,
+}
+
+/// Deployed build and the cluster it indexes, so clients can feature-detect
+pub async fn get_version(State(state): State<AppState>) -> Json<VersionResponse> {
+    Json(VersionResponse {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        program_id: state.program_id.to_string(),
+        network: config::network_for(&state.rpc_client.url()).to_string(),
+        git_commit: option_env!("GIT_COMMIT").map(str::to_string),
+    })
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TransactionResponse {
+    pub signature: String,
+    pub data: serde_json::Value,
+}
+
+/// Fetches a transaction straight from the backend's RPC node, whether or not
+/// it was indexed
+pub async fn get_rpc_transaction(
+    State(state): State<AppState>,
+    Path(signature): Path<String>,
+) -> Result<Json<TransactionResponse>, AppError> {
+    if signature.is_empty() {
+        return Err(AppError::BadRequest(
+            "Transaction signature cannot be empty".to_string(),
+        ));
+    }
+
+    let request_body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "getTransaction",
+        "params": [
+            signature,
+            {
+                "encoding": "jsonParsed",
+                "maxSupportedTransactionVersion": 0
+            }
+        ]
+    });
+
+    let rpc_response = post_json_rpc(&state.rpc_client.url(), &request_body).await?;
+
+    let data = rpc_response["result"].clone();
+
+    if data.is_null() {
+        return Err(AppError::BadRequest(format!(
+            "Transaction with signature '{}' not found or invalid response.",
+            signature
+        )));
+    }
+
+    Ok(Json(TransactionResponse { signature, data }))
+}
+
+/// Sends a JSON-RPC request to `rpc_url` and returns the node's JSON response,
+/// which may hold a JSON-RPC `error` rather than a `result`
+async fn post_json_rpc(
+    rpc_url: &str,
+    request_body: &serde_json::Value,
+) -> Result<serde_json::Value, AppError> {
+    let response = reqwest::Client::new()
+        .post(rpc_url)
+        .json(request_body)
+        .send()
+        .await?;
+
+    if !response.status().is_success() {
+        return Err(AppError::InternalServerError(format!(
+            "RPC request failed: {}",
+            response.status()
+        )));
+    }
+
+    Ok(response.json().await?)
+}
+
+/// A JSON-RPC call for `/rpc`. Anything beyond these fields is dropped.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RpcProxyRequest {
+    pub id: Option<serde_json::Value>,
+    pub method: String,
+    #[serde(default)]
+    pub params: Vec<serde_json::Value>,
+}
+
+/// Forwards an allowlisted read-only JSON-RPC call to the backend's RPC node, so
+/// frontends get raw RPC access without talking to the node directly. Other
+/// methods are refused with `403`.
+pub async fn proxy_rpc(
+    State(state): State<AppState>,
+    Json(request): Json<RpcProxyRequest>,
+) -> Result<Json<serde_json::Value>, AppError> {
+    if !PROXIED_RPC_METHODS.contains(&request.method.as_str()) {
+        return Err(AppError::Forbidden(format!(
+            "RPC method '{}' is not allowed",
+            request.method
+        )));
+    }
+    if request.params.len() > MAX_PROXIED_RPC_PARAMS {
+        return Err(AppError::BadRequest(format!(
+            "At most {} params are accepted",
+            MAX_PROXIED_RPC_PARAMS
+        )));
+    }
+    let id = match request.id {
+        None => serde_json::json!(1),
+        Some(id @ (serde_json::Value::Number(_) | serde_json::Value::String(_))) => id,
+        Some(_) => {
+            return Err(AppError::BadRequest(
+                "id must be a number or a string".to_string(),
+            ))
+        }
+    };
+
+    let request_body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "method": request.method,
+        "params": request.params,
+    });
 
-use crate::{models::Transaction, state::AppState};
+    Ok(Json(
+        post_json_rpc(&state.rpc_client.url(), &request_body).await?,
+    ))
+}
+
+/// Decodes the SolRaiser events of any transaction straight from the RPC, so a
+/// just-submitted transaction can be inspected before the indexer stores it
+pub async fn get_transaction_events(
+    State(state): State<AppState>,
+    Path(signature): Path<String>,
+) -> Result<Json<Vec<CampaignEvent>>, AppError> {
+    let parsed: Signature = signature
+        .parse()
+        .map_err(|_| AppError::BadRequest(format!("Invalid signature '{}'", signature)))?;
+
+    let rpc_client = state.rpc_client.clone();
+    let transaction = tokio::task::spawn_blocking(move || {
+        // `send` rather than `get_transaction_with_config` so an unknown
+        // signature comes back as `None` instead of a decode error
+        rpc_client.send::<Option<EncodedConfirmedTransactionWithStatusMeta>>(
+            RpcRequest::GetTransaction,
+            serde_json::json!([
+                parsed.to_string(),
+                RpcTransactionConfig {
+                    encoding: Some(UiTransactionEncoding::Json),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                },
+            ]),
+        )
+    })
+    .await
+    .context("Failed to spawn transaction lookup")?
+    .context("Failed to fetch transaction")?
+    .ok_or_else(|| AppError::NotFound(format!("Transaction '{}' not found", signature)))?;
+
+    let events = match transaction.transaction.meta.map(|meta| meta.log_messages) {
+        Some(OptionSerializer::Some(logs)) => {
+            SolanaIndexer::parse_anchor_event(&state.program_id, &logs)
+        }
+        _ => Vec::new(),
+    };
+
+    Ok(Json(events))
+}
 
 pub async fn get_indexer_status(
-    state: Arc<AppState>,
+    State(state): State<AppState>,
 ) -> Result<Json<serde_json::Value>, StatusCode> {
     let last_slot = *state
         .last_indexed_slot
@@ -24,46 +287,111 @@ pub async fn get_indexer_status(
         "last_indexed_slot": last_slot,
         "latest_blockchain_slot": latest_slot,
         "slots_behind": latest_slot.saturating_sub(last_slot),
-        "mode": "websocket-program-scoped"
+        "mode": "websocket-program-scoped",
+        "pipeline": state.pipeline_metrics.snapshot()
     })))
 }
 
 #[derive(Debug, Deserialize, Serialize)]
 pub struct PaginationParams {
-    pub limit: i64,
+    pub limit: Option<i64>,
+    #[serde(default)]
     pub offset: i64,
 }
 
+/// Most recently indexed blocks, highest slot first
 pub async fn get_recent_blocks(
-    state: Arc<AppState>,
+    State(state): State<AppState>,
     Query(query): Query<PaginationParams>,
-) -> Result<Json<Vec<Transaction>>, StatusCode> {
-    let transactions = sqlx::query_as::<_, Transaction>(
+) -> Result<Json<Vec<Block>>, AppError> {
+    let blocks = sqlx::query_as::<_, Block>(
         r#"
-        SELECT id, signature, slot, block_time, success, fee, indexed_at
-        FROM transactions ORDER BY slot DESC, id DESC
+        SELECT slot, blockhash, parent_slot, block_time, indexed_at
+        FROM blocks ORDER BY slot DESC
         LIMIT $1 OFFSET $2
         "#,
     )
-    .bind(query.limit)
-    .bind(query.offset)
+    .bind(
+        query
+            .limit
+            .unwrap_or(DEFAULT_BLOCKS_LIMIT)
+            .clamp(1, MAX_BLOCKS_LIMIT),
+    )
+    .bind(query.offset.max(0))
     .fetch_all(&state.db)
-    .await
-    .map_err(|_| StatusCode::INTERNAL_SERVER_ERROR)?;
+    .await?;
 
-    if transactions.is_empty() {
-        return Err(StatusCode::NOT_FOUND);
+    Ok(Json(blocks))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct BlocksQuery {
+    /// Inclusive; defaults to the first indexed slot
+    pub from_slot: Option<i64>,
+    /// Inclusive; defaults to the last indexed slot
+    pub to_slot: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// Indexed blocks within `[from_slot, to_slot]`, lowest slot first. At most
+/// `limit` blocks are returned, so a wide range is read by paging `from_slot`.
+pub async fn get_blocks(
+    State(state): State<AppState>,
+    Query(query): Query<BlocksQuery>,
+) -> Result<Json<Vec<Block>>, AppError> {
+    let from_slot = query.from_slot.unwrap_or(0);
+    let to_slot = query.to_slot.unwrap_or(i64::MAX);
+    if from_slot > to_slot {
+        return Err(AppError::BadRequest(
+            "from_slot must not be greater than to_slot".to_string(),
+        ));
     }
-    Ok(Json(transactions))
+
+    let blocks = sqlx::query_as::<_, Block>(
+        r#"
+        SELECT slot, blockhash, parent_slot, block_time, indexed_at
+        FROM blocks WHERE slot BETWEEN $1 AND $2
+        ORDER BY slot ASC
+        LIMIT $3
+        "#,
+    )
+    .bind(from_slot)
+    .bind(to_slot)
+    .bind(
+        query
+            .limit
+            .unwrap_or(DEFAULT_BLOCKS_LIMIT)
+            .clamp(1, MAX_BLOCKS_LIMIT),
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(blocks))
+}
+
+pub async fn get_block(
+    State(state): State<AppState>,
+    Path(slot): Path<i64>,
+) -> Result<Json<Block>, AppError> {
+    let block = sqlx::query_as::<_, Block>(
+        "SELECT slot, blockhash, parent_slot, block_time, indexed_at FROM blocks WHERE slot = $1",
+    )
+    .bind(slot)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Block at slot {} is not indexed", slot)))?;
+
+    Ok(Json(block))
 }
 
+/// Looks up an indexed transaction, including the failure detail if it reverted
 pub async fn get_transaction_by_signature(
-    state: Arc<AppState>,
+    State(state): State<AppState>,
     Path(signature): Path<String>,
 ) -> Result<Json<Vec<Transaction>>, StatusCode> {
     let transactions = sqlx::query_as::<_, Transaction>(
         r#"
-        SELECT id, signature, slot, block_time, success, fee, indexed_at
+        SELECT id, signature, slot, block_time, success, fee, error_detail, indexed_at
         FROM transactions
         WHERE signature = $1"#,
     )
@@ -78,3 +406,837 @@ pub async fn get_transaction_by_signature(
 
     Ok(Json(transactions))
 }
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EventsQuery {
+    /// Only return events after this slot, or after `since_id` within it
+    #[serde(default)]
+    pub since_slot: i64,
+    /// The `next_cursor.since_id` of the previous page; without it every event
+    /// in `since_slot` is skipped
+    pub since_id: Option<i32>,
+    pub limit: Option<i64>,
+    /// Include each event's `raw_logs`
+    #[serde(default)]
+    pub include_logs: bool,
+}
+
+impl EventsQuery {
+    fn limit(&self) -> i64 {
+        self.limit
+            .unwrap_or(DEFAULT_EVENTS_LIMIT)
+            .clamp(1, MAX_EVENTS_LIMIT)
+    }
+
+    fn cursor(&self) -> EventCursor {
+        EventCursor {
+            since_slot: self.since_slot,
+            since_id: self.since_id.unwrap_or(i32::MAX),
+        }
+    }
+
+    /// `events` as a page ending at the last of them
+    fn page(&self, events: Vec<CampaignEventRecord>) -> EventPage {
+        let next_cursor = events
+            .last()
+            .map(|event| EventCursor {
+                since_slot: event.slot,
+                since_id: event.id,
+            })
+            .unwrap_or_else(|| self.cursor());
+        EventPage {
+            events: with_raw_logs(events, self.include_logs),
+            next_cursor,
+        }
+    }
+}
+
+/// Drops `raw_logs` from `events` unless `include_logs` is set
+fn with_raw_logs(
+    mut events: Vec<CampaignEventRecord>,
+    include_logs: bool,
+) -> Vec<CampaignEventRecord> {
+    if !include_logs {
+        for event in &mut events {
+            event.raw_logs = None;
+        }
+    }
+    events
+}
+
+/// Events after the `(since_slot, since_id)` cursor in ascending `(slot, id)`
+/// order, so polling clients can pass back each page's `next_cursor`
+pub async fn get_events(
+    State(state): State<AppState>,
+    Query(query): Query<EventsQuery>,
+) -> Result<Json<EventPage>, AppError> {
+    let cursor = query.cursor();
+    let events = sqlx::query_as::<_, CampaignEventRecord>(
+        r#"
+        SELECT * FROM campaign_events
+        WHERE (slot, id) > ($1, $2) AND NOT orphaned
+        ORDER BY slot ASC, id ASC
+        LIMIT $3
+        "#,
+    )
+    .bind(cursor.since_slot)
+    .bind(cursor.since_id)
+    .bind(query.limit())
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(query.page(events)))
+}
+
+pub async fn get_campaign_events(
+    State(state): State<AppState>,
+    Path(campaign_id): Path<i64>,
+    Query(query): Query<EventsQuery>,
+) -> Result<Json<EventPage>, AppError> {
+    let cursor = query.cursor();
+    let events = sqlx::query_as::<_, CampaignEventRecord>(
+        r#"
+        SELECT * FROM campaign_events
+        WHERE campaign_id = $1 AND (slot, id) > ($2, $3) AND NOT orphaned
+        ORDER BY slot ASC, id ASC
+        LIMIT $4
+        "#,
+    )
+    .bind(campaign_id)
+    .bind(cursor.since_slot)
+    .bind(cursor.since_id)
+    .bind(query.limit())
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(query.page(events)))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CampaignDetailQuery {
+    /// How many of the most recent events to include
+    pub events: Option<i64>,
+}
+
+/// Summary, status, cached metadata and latest events of one campaign in a
+/// single response
+pub async fn get_campaign_detail(
+    State(state): State<AppState>,
+    Path(campaign_id): Path<i64>,
+    Query(query): Query<CampaignDetailQuery>,
+) -> Result<Json<CampaignDetail>, AppError> {
+    let CampaignListing { summary, status } = sqlx::query_as::<_, CampaignListing>(
+        r#"
+        SELECT s.*, COALESCE(st.status, 'active') AS status
+        FROM campaign_summaries s
+        LEFT JOIN campaign_statuses st ON st.campaign_id = s.campaign_id
+        WHERE s.campaign_id = $1
+        "#,
+    )
+    .bind(campaign_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let metadata = sqlx::query_as::<_, CampaignMetadata>(
+        "SELECT name, description, image_url FROM campaign_metadata WHERE campaign_id = $1",
+    )
+    .bind(campaign_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let recent_events = sqlx::query_as::<_, CampaignEventRecord>(
+        r#"
+        SELECT * FROM campaign_events
+        WHERE campaign_id = $1 AND NOT orphaned
+        ORDER BY slot DESC, id DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(campaign_id)
+    .bind(
+        query
+            .events
+            .unwrap_or(DEFAULT_DETAIL_EVENTS)
+            .clamp(1, MAX_DETAIL_EVENTS),
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(CampaignDetail {
+        summary,
+        status,
+        metadata,
+        recent_events: with_raw_logs(recent_events, false),
+    }))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct TopDonorsQuery {
+    pub limit: Option<i64>,
+}
+
+/// A campaign's donors by total donated, largest first. Anonymous donations
+/// count toward the campaign's total but are left off this list.
+pub async fn get_top_donors(
+    State(state): State<AppState>,
+    Path(campaign_id): Path<i64>,
+    Query(query): Query<TopDonorsQuery>,
+) -> Result<Json<Vec<TopDonor>>, AppError> {
+    let donors = sqlx::query_as::<_, TopDonor>(
+        r#"
+        SELECT user_pubkey AS donor,
+               COALESCE(SUM(amount), 0)::BIGINT AS total_amount,
+               COUNT(*) AS donation_count
+        FROM campaign_events
+        WHERE campaign_id = $1 AND event_type = 'donated' AND NOT orphaned
+          AND user_pubkey <> $2
+        GROUP BY user_pubkey
+        ORDER BY total_amount DESC, donor ASC
+        LIMIT $3
+        "#,
+    )
+    .bind(campaign_id)
+    .bind(ANONYMOUS_DONOR)
+    .bind(
+        query
+            .limit
+            .unwrap_or(DEFAULT_TOP_DONORS_LIMIT)
+            .clamp(1, MAX_TOP_DONORS_LIMIT),
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(donors))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct RefundStatusQuery {
+    pub donor: String,
+}
+
+/// Whether `donor` could reclaim their donations to a campaign. The program has
+/// no refund instruction yet, so no donor is eligible; the reason says why.
+pub async fn get_refund_status(
+    State(state): State<AppState>,
+    Path(campaign_id): Path<i64>,
+    Query(query): Query<RefundStatusQuery>,
+) -> Result<Json<RefundStatus>, AppError> {
+    if query.donor.parse::<Pubkey>().is_err() {
+        return Err(AppError::BadRequest(format!(
+            "Invalid donor pubkey '{}'",
+            query.donor
+        )));
+    }
+
+    let summary = sqlx::query_as::<_, CampaignSummary>(
+        "SELECT * FROM campaign_summaries WHERE campaign_id = $1",
+    )
+    .bind(campaign_id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Campaign {} not found", campaign_id)))?;
+
+    let contributed = sqlx::query_scalar::<_, i64>(
+        r#"
+        SELECT COALESCE(SUM(amount), 0)::BIGINT
+        FROM campaign_events
+        WHERE campaign_id = $1 AND user_pubkey = $2
+          AND event_type = 'donated' AND NOT orphaned
+        "#,
+    )
+    .bind(campaign_id)
+    .bind(&query.donor)
+    .fetch_one(&state.db)
+    .await?;
+
+    Ok(Json(RefundStatus::evaluate(
+        &summary,
+        query.donor,
+        contributed,
+        chrono::Utc::now().timestamp(),
+    )))
+}
+
+#[derive(Debug, Default, Clone, Copy, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressInterval {
+    Hour,
+    #[default]
+    Day,
+}
+
+impl ProgressInterval {
+    /// Field name understood by Postgres `date_trunc`
+    fn as_str(self) -> &'static str {
+        match self {
+            ProgressInterval::Hour => "hour",
+            ProgressInterval::Day => "day",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct ProgressQuery {
+    #[serde(default)]
+    pub interval: ProgressInterval,
+}
+
+/// Cumulative amount raised per time bucket, from the bucket the campaign was
+/// created in up to now or its deadline, whichever is earlier. Buckets without
+/// donations carry the previous total forward.
+pub async fn get_campaign_progress(
+    State(state): State<AppState>,
+    Path(campaign_id): Path<i64>,
+    Query(query): Query<ProgressQuery>,
+) -> Result<Json<Vec<ProgressPoint>>, AppError> {
+    let created = sqlx::query_as::<_, (Option<i64>, Option<i64>)>(
+        r#"
+        SELECT t.block_time, e.deadline
+        FROM campaign_events e
+        JOIN transactions t ON t.signature = e.signature
+        WHERE e.campaign_id = $1 AND e.event_type = 'created' AND NOT e.orphaned
+        "#,
+    )
+    .bind(campaign_id)
+    .fetch_optional(&state.db)
+    .await?;
+
+    let Some((Some(created_at), deadline)) = created else {
+        return Err(AppError::NotFound(format!(
+            "Campaign {} not found",
+            campaign_id
+        )));
+    };
+
+    let series = sqlx::query_as::<_, ProgressPoint>(
+        r#"
+        WITH buckets AS (
+            SELECT generate_series(
+                date_trunc($2, to_timestamp($3), 'UTC'),
+                date_trunc($2, LEAST(now(), COALESCE(to_timestamp($4), now())), 'UTC'),
+                ('1 ' || $2)::interval
+            ) AS t
+        ),
+        donations AS (
+            SELECT date_trunc($2, to_timestamp(t.block_time), 'UTC') AS bucket,
+                   SUM(e.amount) AS amount
+            FROM campaign_events e
+            JOIN transactions t ON t.signature = e.signature
+            WHERE e.campaign_id = $1 AND e.event_type = 'donated' AND NOT e.orphaned
+            GROUP BY bucket
+        )
+        SELECT b.t,
+               (SUM(COALESCE(d.amount, 0)) OVER (ORDER BY b.t))::BIGINT AS cumulative_amount
+        FROM buckets b
+        LEFT JOIN donations d ON d.bucket = b.t
+        ORDER BY b.t
+        "#,
+    )
+    .bind(campaign_id)
+    .bind(query.interval.as_str())
+    .bind(created_at as f64)
+    .bind(deadline.map(|d| d as f64))
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(series))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct DonorHistoryQuery {
+    pub campaign_id: Option<i64>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// A donor's donations across campaigns, newest first, optionally narrowed to
+/// one campaign. The totals cover all matching donations, not just this page.
+pub async fn get_donor_history(
+    State(state): State<AppState>,
+    Path(donor): Path<String>,
+    Query(query): Query<DonorHistoryQuery>,
+) -> Result<Json<DonorHistory>, AppError> {
+    if donor.parse::<Pubkey>().is_err() {
+        return Err(AppError::BadRequest(format!(
+            "Invalid donor pubkey '{}'",
+            donor
+        )));
+    }
+
+    let (total_amount, donation_count) = sqlx::query_as::<_, (i64, i64)>(
+        r#"
+        SELECT COALESCE(SUM(amount), 0)::BIGINT, COUNT(*)
+        FROM campaign_events
+        WHERE event_type = 'donated' AND user_pubkey = $1 AND NOT orphaned
+          AND ($2::BIGINT IS NULL OR campaign_id = $2)
+        "#,
+    )
+    .bind(&donor)
+    .bind(query.campaign_id)
+    .fetch_one(&state.db)
+    .await?;
+
+    let donations = sqlx::query_as::<_, DonorDonation>(
+        r#"
+        SELECT e.signature, e.slot, t.block_time, e.campaign_id,
+               m.name AS campaign_name, COALESCE(e.amount, 0) AS amount
+        FROM campaign_events e
+        LEFT JOIN transactions t ON t.signature = e.signature
+        LEFT JOIN campaign_metadata m ON m.campaign_id = e.campaign_id
+        WHERE e.event_type = 'donated' AND e.user_pubkey = $1 AND NOT e.orphaned
+          AND ($2::BIGINT IS NULL OR e.campaign_id = $2)
+        ORDER BY e.slot DESC, e.id DESC
+        LIMIT $3 OFFSET $4
+        "#,
+    )
+    .bind(&donor)
+    .bind(query.campaign_id)
+    .bind(
+        query
+            .limit
+            .unwrap_or(DEFAULT_EVENTS_LIMIT)
+            .clamp(1, MAX_EVENTS_LIMIT),
+    )
+    .bind(query.offset.unwrap_or(0).max(0))
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(DonorHistory {
+        donor,
+        total_amount,
+        donation_count,
+        donations,
+    }))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct EndingSoonQuery {
+    /// Only campaigns whose deadline is at most this many hours away; capped
+    /// at a year
+    pub within_hours: Option<i64>,
+    pub limit: Option<i64>,
+}
+
+/// Campaigns whose status is `active` and whose deadline is still ahead, closest
+/// deadline first
+pub async fn get_ending_soon_campaigns(
+    State(state): State<AppState>,
+    Query(query): Query<EndingSoonQuery>,
+) -> Result<Json<Vec<EndingSoonCampaign>>, AppError> {
+    if query.within_hours.is_some_and(|hours| hours <= 0) {
+        return Err(AppError::BadRequest(
+            "within_hours must be greater than 0".to_string(),
+        ));
+    }
+
+    let campaigns = sqlx::query_as::<_, EndingSoonCampaign>(
+        r#"
+        SELECT s.*, m.name, m.image_url,
+               (s.deadline - EXTRACT(EPOCH FROM now())::BIGINT) AS seconds_remaining
+        FROM campaign_summaries s
+        LEFT JOIN campaign_metadata m ON m.campaign_id = s.campaign_id
+        LEFT JOIN campaign_statuses st ON st.campaign_id = s.campaign_id
+        WHERE COALESCE(st.status, 'active') = 'active'
+          -- campaign_statuses lags by up to a sweep interval
+          AND s.deadline > EXTRACT(EPOCH FROM now())::BIGINT
+          AND ($1::BIGINT IS NULL
+               OR s.deadline <= EXTRACT(EPOCH FROM now())::BIGINT + $1 * 3600)
+        ORDER BY s.deadline ASC, s.campaign_id ASC
+        LIMIT $2
+        "#,
+    )
+    .bind(
+        query
+            .within_hours
+            .map(|hours| hours.min(MAX_ENDING_SOON_HOURS)),
+    )
+    .bind(
+        query
+            .limit
+            .unwrap_or(DEFAULT_CAMPAIGNS_LIMIT)
+            .clamp(1, MAX_CAMPAIGNS_LIMIT),
+    )
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(campaigns))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct CampaignsQuery {
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// One page of campaigns in `campaign_id` order. Use `/campaigns/stream` to
+/// read them all.
+pub async fn get_campaigns(
+    State(state): State<AppState>,
+    Query(query): Query<CampaignsQuery>,
+) -> Result<Json<Vec<CampaignListing>>, AppError> {
+    let campaigns = sqlx::query_as::<_, CampaignListing>(
+        r#"
+        SELECT s.*, COALESCE(st.status, 'active') AS status
+        FROM campaign_summaries s
+        LEFT JOIN campaign_statuses st ON st.campaign_id = s.campaign_id
+        ORDER BY s.campaign_id ASC
+        LIMIT $1 OFFSET $2
+        "#,
+    )
+    .bind(
+        query
+            .limit
+            .unwrap_or(DEFAULT_CAMPAIGNS_LIMIT)
+            .clamp(1, MAX_CAMPAIGNS_LIMIT),
+    )
+    .bind(query.offset.unwrap_or(0).max(0))
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(campaigns))
+}
+
+/// Every campaign as newline-delimited JSON, in `campaign_id` order. Rows are
+/// written as the database cursor yields them, so the whole list is never held
+/// in memory; a database error mid-stream aborts the response.
+pub async fn stream_campaigns(State(state): State<AppState>) -> Response {
+    let (tx, mut rx) = tokio::sync::mpsc::channel(CAMPAIGN_STREAM_BUFFER);
+
+    tokio::spawn(async move {
+        let mut rows = sqlx::query_as::<_, CampaignListing>(
+            r#"
+            SELECT s.*, COALESCE(st.status, 'active') AS status
+            FROM campaign_summaries s
+            LEFT JOIN campaign_statuses st ON st.campaign_id = s.campaign_id
+            ORDER BY s.campaign_id ASC
+            "#,
+        )
+        .fetch(&state.db);
+
+        loop {
+            let line = match rows.try_next().await {
+                Ok(Some(campaign)) => serde_json::to_vec(&campaign)
+                    .map(|mut line| {
+                        line.push(b'\n');
+                        line
+                    })
+                    .map_err(anyhow::Error::from),
+                Ok(None) => break,
+                Err(e) => {
+                    error!("Campaign stream error: {:?}", e);
+                    Err(e.into())
+                }
+            };
+            let failed = line.is_err();
+            // Fails once the client has gone away
+            if tx.send(line).await.is_err() || failed {
+                break;
+            }
+        }
+    });
+
+    let lines = futures::stream::poll_fn(move |cx| rx.poll_recv(cx));
+    (
+        [(header::CONTENT_TYPE, "application/x-ndjson")],
+        Body::from_stream(lines),
+    )
+        .into_response()
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SearchQuery {
+    pub q: Option<String>,
+    pub limit: Option<i64>,
+    pub offset: Option<i64>,
+}
+
+/// Full-text search over cached campaign names and descriptions, best match first
+pub async fn search_campaigns(
+    State(state): State<AppState>,
+    Query(query): Query<SearchQuery>,
+) -> Result<Json<Vec<CampaignSearchResult>>, AppError> {
+    let q = query.q.as_deref().map(str::trim).unwrap_or_default();
+    if q.is_empty() {
+        return Err(AppError::BadRequest(
+            "Search query 'q' cannot be empty".to_string(),
+        ));
+    }
+
+    let results = sqlx::query_as::<_, CampaignSearchResult>(
+        r#"
+        SELECT s.*, m.name, m.description, m.image_url,
+               ts_rank(m.search_vector, query) AS rank
+        FROM campaign_metadata m
+        JOIN campaign_summaries s ON s.campaign_id = m.campaign_id,
+             websearch_to_tsquery('english', $1) query
+        WHERE m.search_vector @@ query
+        ORDER BY rank DESC, s.campaign_id DESC
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(q)
+    .bind(
+        query
+            .limit
+            .unwrap_or(DEFAULT_SEARCH_LIMIT)
+            .clamp(1, MAX_SEARCH_LIMIT),
+    )
+    .bind(query.offset.unwrap_or(0).max(0))
+    .fetch_all(&state.db)
+    .await?;
+
+    Ok(Json(results))
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+pub struct FeeEstimateQuery {
+    /// Creator the estimate is for; without one it assumes their first campaign
+    pub creator: Option<String>,
+}
+
+/// Estimated SOL cost of creating a campaign: the campaign account's rent, the
+/// creator stats account's rent if the creator has none yet, the signature fee
+/// and the priority fee `build-create-tx` sets
+pub async fn get_fee_estimate(
+    State(state): State<AppState>,
+    Query(query): Query<FeeEstimateQuery>,
+) -> Result<Json<FeeEstimate>, AppError> {
+    let creator = query
+        .creator
+        .map(|creator| {
+            creator
+                .parse::<Pubkey>()
+                .map_err(|_| AppError::BadRequest(format!("Invalid creator '{}'", creator)))
+        })
+        .transpose()?;
+
+    let rpc_client = state.rpc_client.clone();
+    let program_id = state.program_id;
+    let cached_rent = state.campaign_rent.get().copied();
+    let cached_creator_stats_rent = state.creator_stats_rent.get().copied();
+    let (rent, has_creator_stats, creator_stats_rent, priority_fee) =
+        tokio::task::spawn_blocking(move || {
+            let rent = match cached_rent {
+                Some(rent) => Ok(rent),
+                None => rpc_client.get_minimum_balance_for_rent_exemption(CAMPAIGN_ACCOUNT_LEN),
+            };
+            let has_creator_stats = match creator {
+                Some(creator) => rpc_client
+                    .get_account_with_commitment(
+                        &pda::derive_creator_stats_pda(&program_id, &creator).0,
+                        rpc_client.commitment(),
+                    )
+                    .map(|response| response.value.is_some()),
+                None => Ok(false),
+            };
+            let creator_stats_rent = match (&has_creator_stats, cached_creator_stats_rent) {
+                (Ok(true), _) => Ok(0),
+                (_, Some(rent)) => Ok(rent),
+                _ => rpc_client.get_minimum_balance_for_rent_exemption(CREATOR_STATS_ACCOUNT_LEN),
+            };
+            let priority_fee = median_priority_fee(&rpc_client, &program_id);
+            (rent, has_creator_stats, creator_stats_rent, priority_fee)
+        })
+        .await
+        .context("Failed to spawn fee lookup")?;
+    let rent = rent.context("Failed to fetch rent-exempt minimum")?;
+    let has_creator_stats =
+        has_creator_stats.context("Failed to look up the creator's stats account")?;
+    let creator_stats_rent = creator_stats_rent.context("Failed to fetch rent-exempt minimum")?;
+    let priority_fee = priority_fee.context("Failed to fetch recent prioritization fees")?;
+
+    let _ = state.campaign_rent.set(rent);
+    if !has_creator_stats {
+        let _ = state.creator_stats_rent.set(creator_stats_rent);
+    }
+
+    Ok(Json(FeeEstimate::new(
+        rent,
+        creator_stats_rent,
+        priority_fee,
+    )))
+}
+
+/// Median prioritization fee recently paid for the program, in micro-lamports
+/// per compute unit; 0 if the node has seen none
+fn median_priority_fee(
+    rpc_client: &RpcClient,
+    program_id: &Pubkey,
+) -> solana_client::client_error::Result<u64> {
+    let mut fees: Vec<u64> = rpc_client
+        .get_recent_prioritization_fees(&[*program_id])?
+        .into_iter()
+        .map(|fee| fee.prioritization_fee)
+        .collect();
+    fees.sort_unstable();
+    Ok(fees.get(fees.len() / 2).copied().unwrap_or(0))
+}
+
+/// Builds an unsigned `create_campaign` transaction with the creator as fee payer,
+/// for the campaign id the on-chain registry will assign next. The campaign's
+/// name, description and image are hosted at `/v1/metadata/:id`, which becomes
+/// its `metadata_url`. It requests
+/// [`FeeEstimate::CREATE_CAMPAIGN_COMPUTE_UNITS`] at the median recent priority
+/// fee, as quoted by `/campaigns/fee-estimate`.
+pub async fn build_create_campaign_tx(
+    State(state): State<AppState>,
+    Json(request): Json<CampaignCreateRequest>,
+) -> Result<Json<CampaignCreateTransaction>, AppError> {
+    if request.target_amount == 0 {
+        return Err(AppError::BadRequest(
+            "target_amount must be greater than 0".to_string(),
+        ));
+    }
+    if request.duration == 0 {
+        return Err(AppError::BadRequest(
+            "duration must be greater than 0".to_string(),
+        ));
+    }
+    if request.name.trim().is_empty() {
+        return Err(AppError::BadRequest("name must not be empty".to_string()));
+    }
+    if metadata::resolve_url(&request.image_url).is_none() {
+        return Err(AppError::BadRequest(
+            "image_url must be an http(s) or ipfs URL".to_string(),
+        ));
+    }
+    let document = CampaignMetadata {
+        name: Some(request.name.clone()),
+        description: Some(request.description.clone()),
+        image_url: Some(request.image_url.clone()),
+    };
+    // The indexer won't read back a larger document
+    if serde_json::to_vec(&document).map_or(true, |json| json.len() > metadata::MAX_METADATA_BYTES)
+    {
+        return Err(AppError::BadRequest(format!(
+            "name, description and image_url must fit in a {}-byte document",
+            metadata::MAX_METADATA_BYTES
+        )));
+    }
+    let public_url = state.public_url.clone().ok_or_else(|| {
+        AppError::InternalServerError(
+            "PUBLIC_URL must be set to host campaign metadata".to_string(),
+        )
+    })?;
+    let creator: Pubkey = request
+        .creator
+        .parse()
+        .map_err(|_| AppError::BadRequest(format!("Invalid creator '{}'", request.creator)))?;
+    let symbol_str = request.symbol.as_deref().unwrap_or(DEFAULT_SYMBOL);
+    let symbol = instructions::encode_symbol(symbol_str).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "symbol '{}' must be at most {} ASCII characters",
+            symbol_str,
+            instructions::SYMBOL_LEN
+        ))
+    })?;
+
+    let now = chrono::Utc::now();
+    let deadline = i64::try_from(request.duration)
+        .ok()
+        .and_then(|duration| now.timestamp().checked_add(duration))
+        .ok_or_else(|| AppError::BadRequest("duration is too large".to_string()))?;
+
+    let rpc_client = state.rpc_client.clone();
+    let registry_pda = pda::derive_registry_pda(&state.program_id).0;
+    let program_id = state.program_id;
+    let cached_rent = state.campaign_rent.get().copied();
+    let (rent, registry_data, recent_blockhash, priority_fee) =
+        tokio::task::spawn_blocking(move || {
+            let rent = match cached_rent {
+                Some(rent) => Ok(rent),
+                None => rpc_client.get_minimum_balance_for_rent_exemption(CAMPAIGN_ACCOUNT_LEN),
+            };
+            let registry_data = rpc_client.get_account_data(&registry_pda);
+            let recent_blockhash = rpc_client.get_latest_blockhash();
+            let priority_fee = median_priority_fee(&rpc_client, &program_id);
+            (rent, registry_data, recent_blockhash, priority_fee)
+        })
+        .await
+        .context("Failed to spawn registry and blockhash lookup")?;
+    let rent = rent.context("Failed to fetch rent-exempt minimum")?;
+    let _ = state.campaign_rent.set(rent);
+    // The program would reject it, after the creator paid the fee
+    let min_goal = accounts::min_goal_amount(rent);
+    if request.target_amount < min_goal {
+        return Err(AppError::BadRequest(format!(
+            "target_amount must be at least {} lamports",
+            min_goal
+        )));
+    }
+    let registry_data = registry_data.context("Failed to fetch the campaign registry")?;
+    let recent_blockhash = recent_blockhash.context("Failed to fetch latest blockhash")?;
+    let priority_fee = priority_fee.context("Failed to fetch recent prioritization fees")?;
+    let campaign_id = RegistryAccount::decode(&registry_data)?.next_id;
+
+    let metadata_id = metadata::host_metadata(
+        &state.db,
+        &request.name,
+        &request.description,
+        &request.image_url,
+    )
+    .await?;
+    let metadata_url = format!("{}/v1/metadata/{}", public_url, metadata_id);
+    if !instructions::is_valid_metadata_url(&metadata_url) {
+        return Err(AppError::InternalServerError(format!(
+            "PUBLIC_URL is too long for a {}-byte metadata_url",
+            instructions::MAX_METADATA_URL_LEN
+        )));
+    }
+
+    let instruction = instructions::create_campaign(
+        &state.program_id,
+        &creator,
+        campaign_id,
+        &instructions::CreateCampaignArgs {
+            goal_amount: request.target_amount,
+            deadline,
+            metadata_url: metadata_url.clone(),
+            withdraw_cooldown_secs: request.withdraw_cooldown_secs,
+            decimals: request.decimals.unwrap_or(DEFAULT_DECIMALS),
+            symbol,
+            beneficiaries: Vec::new(),
+        },
+    );
+
+    let mut transaction = SolanaTransaction::new_with_payer(
+        &[
+            instructions::set_compute_unit_limit(FeeEstimate::CREATE_CAMPAIGN_COMPUTE_UNITS),
+            instructions::set_compute_unit_price(priority_fee),
+            instruction,
+        ],
+        Some(&creator),
+    );
+    transaction.message.recent_blockhash = recent_blockhash;
+
+    let serialized = bincode::serialize(&transaction).context("Failed to serialize transaction")?;
+
+    Ok(Json(CampaignCreateTransaction {
+        transaction: base64::engine::general_purpose::STANDARD.encode(serialized),
+        campaign_id,
+        campaign_pda: pda::derive_campaign_pda(&state.program_id, &creator, campaign_id)
+            .0
+            .to_string(),
+        deadline,
+        recent_blockhash: recent_blockhash.to_string(),
+        metadata_url,
+    }))
+}
+
+/// A metadata document hosted by `build-create-tx`, in the shape the indexer
+/// reads back from a `metadata_url`
+pub async fn get_hosted_metadata(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+) -> Result<Json<CampaignMetadata>, AppError> {
+    let metadata = sqlx::query_as::<_, CampaignMetadata>(
+        "SELECT name, description, image_url FROM hosted_metadata WHERE id = $1",
+    )
+    .bind(&id)
+    .fetch_optional(&state.db)
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("Metadata '{}' not found", id)))?;
+
+    Ok(Json(metadata))
+}
@@ -0,0 +1,179 @@
+//! `Idempotency-Key` support for POST endpoints. A client that retries a POST
+//! with the same key gets the first response back instead of the work being
+//! redone; reusing a key for a different request is rejected.
+
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    body::{to_bytes, Body, Bytes},
+    extract::{Request, State},
+    http::{header, HeaderName, HeaderValue, StatusCode},
+    middleware::Next,
+    response::Response,
+};
+use solana_sdk::hash::{hashv, Hash};
+
+use crate::{error::AppError, state::AppState};
+
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+/// Set on responses served from the cache rather than by the handler
+pub const IDEMPOTENT_REPLAYED: HeaderName = HeaderName::from_static("idempotent-replayed");
+
+/// How long a response stays replayable. Cached transactions carry a recent
+/// blockhash, which expires after 150 slots (about a minute), so a replay must
+/// not outlive it.
+pub const IDEMPOTENCY_TTL: Duration = Duration::from_secs(60);
+/// Cached responses kept at most; storing one more evicts the oldest
+pub const DEFAULT_MAX_IDEMPOTENCY_ENTRIES: usize = 10_000;
+const MAX_KEY_LEN: usize = 255;
+// Same as axum's default `Json` body limit
+const MAX_BODY_BYTES: usize = 2 * 1024 * 1024;
+
+struct CachedResponse {
+    /// Hash of the path and body of the request that produced the response
+    request_hash: Hash,
+    status: StatusCode,
+    content_type: Option<HeaderValue>,
+    body: Bytes,
+    expires_at: Instant,
+}
+
+/// Responses by idempotency key, kept in memory for [`IDEMPOTENCY_TTL`]
+pub struct IdempotencyCache {
+    max_entries: usize,
+    entries: Mutex<HashMap<String, CachedResponse>>,
+}
+
+impl Default for IdempotencyCache {
+    fn default() -> Self {
+        IdempotencyCache::new(DEFAULT_MAX_IDEMPOTENCY_ENTRIES)
+    }
+}
+
+enum Lookup {
+    Miss,
+    Replay(Response),
+    Conflict,
+}
+
+impl IdempotencyCache {
+    /// Keeps at most `max_entries` responses
+    pub fn new(max_entries: usize) -> Self {
+        IdempotencyCache {
+            max_entries,
+            entries: Mutex::default(),
+        }
+    }
+
+    /// Finds the response cached for `key`, dropping expired entries on the way
+    fn lookup(&self, key: &str, request_hash: &Hash, now: Instant) -> Lookup {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|_, cached| cached.expires_at > now);
+
+        match entries.get(key) {
+            None => Lookup::Miss,
+            Some(cached) if cached.request_hash != *request_hash => Lookup::Conflict,
+            Some(cached) => {
+                let mut response = Response::new(Body::from(cached.body.clone()));
+                *response.status_mut() = cached.status;
+                if let Some(content_type) = &cached.content_type {
+                    response
+                        .headers_mut()
+                        .insert(header::CONTENT_TYPE, content_type.clone());
+                }
+                response
+                    .headers_mut()
+                    .insert(IDEMPOTENT_REPLAYED, HeaderValue::from_static("true"));
+                Lookup::Replay(response)
+            }
+        }
+    }
+
+    /// Caches `response` under `key`, first evicting the entries closest to
+    /// expiry if the cache is full
+    fn store(&self, key: String, response: CachedResponse) {
+        let mut entries = self.entries.lock().unwrap();
+        while entries.len() >= self.max_entries && !entries.contains_key(&key) {
+            let Some(oldest) = entries
+                .iter()
+                .min_by_key(|(_, cached)| cached.expires_at)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            entries.remove(&oldest);
+        }
+        entries.insert(key, response);
+    }
+}
+
+/// Middleware for POST routes. Requests without an `Idempotency-Key` header pass
+/// straight through. Server errors are not cached, so a retry after one runs the
+/// handler again; two requests racing with the same new key may both run.
+pub async fn idempotency(
+    State(state): State<AppState>,
+    request: Request,
+    next: Next,
+) -> Result<Response, AppError> {
+    let Some(key) = request.headers().get(&IDEMPOTENCY_KEY) else {
+        return Ok(next.run(request).await);
+    };
+    let key = key
+        .to_str()
+        .ok()
+        .filter(|key| !key.is_empty() && key.len() <= MAX_KEY_LEN)
+        .ok_or_else(|| {
+            AppError::BadRequest(format!(
+                "Idempotency-Key must be 1 to {} visible ASCII characters",
+                MAX_KEY_LEN
+            ))
+        })?
+        .to_string();
+
+    let (parts, body) = request.into_parts();
+    let body = to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|e| AppError::BadRequest(format!("Failed to read request body: {}", e)))?;
+    let request_hash = hashv(&[parts.uri.path().as_bytes(), &body]);
+
+    match state
+        .idempotency
+        .lookup(&key, &request_hash, Instant::now())
+    {
+        Lookup::Replay(response) => return Ok(response),
+        Lookup::Conflict => {
+            return Err(AppError::Conflict(format!(
+                "Idempotency-Key '{}' was already used for a different request",
+                key
+            )))
+        }
+        Lookup::Miss => {}
+    }
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+    if response.status().is_server_error() {
+        return Ok(response);
+    }
+
+    let (parts, body) = response.into_parts();
+    let body = to_bytes(body, usize::MAX)
+        .await
+        .map_err(|e| AppError::InternalServerError(format!("Failed to read response: {}", e)))?;
+
+    state.idempotency.store(
+        key,
+        CachedResponse {
+            request_hash,
+            status: parts.status,
+            content_type: parts.headers.get(header::CONTENT_TYPE).cloned(),
+            body: body.clone(),
+            expires_at: Instant::now() + IDEMPOTENCY_TTL,
+        },
+    );
+
+    Ok(Response::from_parts(parts, Body::from(body)))
+}
@@ -1,32 +1,306 @@
+// The `sqlx::query!` calls below are checked against the schema at compile
+// time. Builds without a database use the committed `.sqlx` query data
+// (`SQLX_OFFLINE=true`), so after adding or changing a query or migration run
+// `sqlx migrate run && cargo sqlx prepare -- --all-targets` and commit `.sqlx`.
+
 use anyhow::{Context, Result};
 use base64::Engine;
-use borsh::BorshDeserialize;
+use borsh::{BorshDeserialize, BorshSerialize};
+use serde::{Serialize, Serializer};
 use solana_client::{
     pubsub_client::PubsubClient,
+    rpc_client::{GetConfirmedSignaturesForAddress2Config, RpcClient},
     rpc_config::{
         CommitmentConfig, RpcTransactionConfig, RpcTransactionLogsConfig,
         RpcTransactionLogsFilter,
     },
+    rpc_response::RpcConfirmedTransactionStatusWithSignature,
 };
-use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use solana_sdk::{hash::hashv, pubkey::Pubkey, signature::Signature};
 use solana_transaction_status::{
-    EncodedConfirmedTransactionWithStatusMeta,
-    UiTransactionEncoding,
+    option_serializer::OptionSerializer, EncodedConfirmedTransactionWithStatusMeta,
+    UiTransactionEncoding, UiTransactionStatusMeta,
+};
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        Arc,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
 };
-use std::sync::Arc;
 use tokio::sync::mpsc;
-use tracing::{error, info, warn};
+use tracing::{debug, error, info, warn};
 
-use crate::state::AppState;
+use crate::{config::IndexEventTypes, metadata, state::AppState};
 
-pub const CHANNEL_BUFFER_SIZE: usize = 1000;
+/// `user_pubkey` stored for donations made with `donate_anonymous`, whose events
+/// carry `Pubkey::default()` instead of the donor
+pub const ANONYMOUS_DONOR: &str = "anonymous";
+/// Queue depth, as a percentage of the buffer, at which the pipeline counts as saturated
+const CHANNEL_SATURATION_PERCENT: usize = 80;
 const ANCHOR_EVENT_DISCRIMINATOR: &str = "Program data: ";
+const PROGRAM_LOG_PREFIX: &str = "Program log: ";
+/// `getSignaturesForAddress` page size (the RPC maximum)
+const BACKFILL_PAGE_LIMIT: usize = 1000;
+/// First reconnect delay after a failed subscription; doubles per further failure
+const RECONNECT_BASE_DELAY: Duration = Duration::from_secs(1);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(60);
+/// Consecutive subscription failures after which reconnects are logged as errors
+const RECONNECT_ALERT_THRESHOLD: u32 = 5;
+/// Processing attempts, including the first, before a message is dead-lettered
+const MAX_PROCESS_ATTEMPTS: u32 = 5;
+/// Delay before a failed message's first retry; doubles per further retry
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(2);
+
+/// Delay before the `attempt`-th consecutive reconnect (1-based), doubling from
+/// [`RECONNECT_BASE_DELAY`] up to [`RECONNECT_MAX_DELAY`]
+pub fn reconnect_backoff(attempt: u32) -> Duration {
+    let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+    RECONNECT_BASE_DELAY
+        .saturating_mul(factor)
+        .min(RECONNECT_MAX_DELAY)
+}
+
+/// Picks a delay between half and all of `delay`, so indexers that lost the
+/// same RPC don't all reconnect in lockstep
+fn with_jitter(delay: Duration) -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|now| now.subsec_nanos())
+        .unwrap_or(0);
+    delay / 2 + delay.mul_f64(f64::from(nanos % 1000) / 2000.0)
+}
+
+/// Parses the `PROGRAM_ID` setting, naming it in the error
+pub fn parse_program_id(program_id: &str) -> Result<Pubkey> {
+    program_id
+        .parse()
+        .with_context(|| format!("invalid PROGRAM_ID '{}'", program_id))
+}
+
+/// First 8 bytes of `sha256("event:<name>")`, Anchor's event discriminator
+pub fn event_discriminator(name: &str) -> [u8; 8] {
+    let hash = hashv(&[b"event:", name.as_bytes()]);
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash.to_bytes()[..8]);
+    discriminator
+}
+
+/// The program named by a `Program <id> invoke [<depth>]` log line
+fn invoked_program(log: &str) -> Option<&str> {
+    let (program, rest) = log.strip_prefix("Program ")?.split_once(' ')?;
+    rest.starts_with("invoke [").then_some(program)
+}
+
+/// Whether `log` is the `Program <id> success` or `Program <id> failed: ...` line
+/// ending an invocation
+fn is_program_return(log: &str) -> bool {
+    log.strip_prefix("Program ")
+        .and_then(|log| log.split_once(' '))
+        .is_some_and(|(_, rest)| rest == "success" || rest.starts_with("failed"))
+}
+
+/// Pubkeys as base58 strings rather than serde's default byte array
+fn serialize_pubkey<S: Serializer>(pubkey: &Pubkey, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(pubkey)
+}
+
+/// A campaign's fixed-width symbol without its zero padding; `None` if unset
+pub fn symbol_to_string(symbol: &[u8; 8]) -> Option<String> {
+    let end = symbol.iter().position(|&b| b == 0).unwrap_or(symbol.len());
+    (end > 0).then(|| String::from_utf8_lossy(&symbol[..end]).into_owned())
+}
+
+fn serialize_symbol<S: Serializer>(symbol: &[u8; 8], serializer: S) -> Result<S::Ok, S::Error> {
+    Serialize::serialize(&symbol_to_string(symbol), serializer)
+}
 
 #[derive(Clone)]
 pub struct SolanaIndexer {
     state: Arc<AppState>,
     program_id: Pubkey,
     ws_url: String,
+    channel_buffer_size: usize,
+    index_event_types: IndexEventTypes,
+}
+
+/// Counters describing how well processing keeps up with ingestion
+#[derive(Debug, Default)]
+pub struct PipelineMetrics {
+    /// Messages queued while the channel was at or above the saturation threshold
+    pub saturated_sends: AtomicU64,
+    /// Deepest the processing queue has been
+    pub max_queue_depth: AtomicUsize,
+    /// Log subscription attempts that have failed in a row; 0 while connected
+    pub subscription_failures: AtomicU64,
+    /// Transactions the RPC returned without meta or logs, whose events were lost
+    pub transactions_without_logs: AtomicU64,
+}
+
+impl PipelineMetrics {
+    pub fn snapshot(&self) -> serde_json::Value {
+        serde_json::json!({
+            "saturated_sends": self.saturated_sends.load(Ordering::Relaxed),
+            "max_queue_depth": self.max_queue_depth.load(Ordering::Relaxed),
+            "subscription_failures": self.subscription_failures.load(Ordering::Relaxed),
+            "transactions_without_logs": self.transactions_without_logs.load(Ordering::Relaxed),
+        })
+    }
+}
+
+/// Ingestion side of the processing channel, reporting backpressure through
+/// [`PipelineMetrics`]. Sends still wait for room instead of dropping: a
+/// dropped signature would be an event that is never indexed.
+#[derive(Clone)]
+pub struct PipelineSender {
+    tx: mpsc::Sender<LogMessage>,
+    metrics: Arc<PipelineMetrics>,
+    saturated: Arc<AtomicBool>,
+}
+
+impl PipelineSender {
+    pub fn new(tx: mpsc::Sender<LogMessage>, metrics: Arc<PipelineMetrics>) -> Self {
+        PipelineSender {
+            tx,
+            metrics,
+            saturated: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub async fn send(
+        &self,
+        log_msg: LogMessage,
+    ) -> std::result::Result<(), mpsc::error::SendError<LogMessage>> {
+        let max = self.tx.max_capacity();
+        let depth = max - self.tx.capacity();
+        self.metrics
+            .max_queue_depth
+            .fetch_max(depth, Ordering::Relaxed);
+
+        if depth * 100 >= max * CHANNEL_SATURATION_PERCENT {
+            self.metrics.saturated_sends.fetch_add(1, Ordering::Relaxed);
+            // Warn once per saturation episode rather than on every send
+            if !self.saturated.swap(true, Ordering::Relaxed) {
+                warn!(
+                    "Processing channel is {}/{} full, processing is falling behind ingestion",
+                    depth, max
+                );
+            }
+        } else if self.saturated.swap(false, Ordering::Relaxed) {
+            info!("Processing channel drained to {}/{}", depth, max);
+        }
+
+        self.tx.send(log_msg).await
+    }
+}
+
+/// How failed log messages are retried before being given up on
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Attempts, including the first
+    pub max_attempts: u32,
+    /// Delay before the first retry; doubles per further retry
+    pub base_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy {
+            max_attempts: MAX_PROCESS_ATTEMPTS,
+            base_delay: RETRY_BASE_DELAY,
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the `retry`-th retry (1-based)
+    fn delay(&self, retry: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+    }
+}
+
+/// Feeds each message from `rx` to `process`. A message that fails is requeued
+/// after [`RetryPolicy::delay`] without holding up the messages behind it; once
+/// it has failed `max_attempts` times it is handed to `dead_letter` with the
+/// last error. Returns when `rx` is closed and no retries are pending.
+pub async fn process_with_retries<P, PF, D, DF>(
+    mut rx: mpsc::Receiver<LogMessage>,
+    policy: RetryPolicy,
+    process: P,
+    dead_letter: D,
+) where
+    P: Fn(LogMessage) -> PF,
+    PF: Future<Output = Result<()>>,
+    D: Fn(LogMessage, u32, anyhow::Error) -> DF,
+    DF: Future<Output = Result<()>>,
+{
+    let (retry_tx, mut retry_rx) = mpsc::unbounded_channel::<(LogMessage, u32)>();
+    let mut pending_retries = 0usize;
+    let mut input_open = true;
+
+    loop {
+        let (log_msg, attempts) = tokio::select! {
+            log_msg = rx.recv(), if input_open => match log_msg {
+                Some(log_msg) => (log_msg, 0),
+                None => {
+                    input_open = false;
+                    continue;
+                }
+            },
+            Some(retry) = retry_rx.recv(), if pending_retries > 0 => {
+                pending_retries -= 1;
+                retry
+            }
+            else => break,
+        };
+
+        let e = match process(log_msg.clone()).await {
+            std::result::Result::Ok(()) => continue,
+            std::result::Result::Err(e) => e,
+        };
+        let attempts = attempts + 1;
+
+        if attempts < policy.max_attempts {
+            let delay = policy.delay(attempts);
+            warn!(
+                "Processing {} failed (attempt {}/{}): {:?}, retrying in {:?}",
+                log_msg.signature, attempts, policy.max_attempts, e, delay
+            );
+            pending_retries += 1;
+            let retry_tx = retry_tx.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(delay).await;
+                let _ = retry_tx.send((log_msg, attempts));
+            });
+        } else {
+            error!(
+                "Giving up on {} after {} attempts: {:?}",
+                log_msg.signature, attempts, e
+            );
+            let signature = log_msg.signature.clone();
+            if let Err(e) = dead_letter(log_msg, attempts, e).await {
+                error!("Failed to record {} as failed: {:?}", signature, e);
+            }
+        }
+    }
+}
+
+/// Why a fetched transaction has no program logs to parse events from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MissingLogs {
+    NoMeta,
+    NoLogMessages,
+}
+
+impl std::fmt::Display for MissingLogs {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MissingLogs::NoMeta => write!(f, "no meta"),
+            MissingLogs::NoLogMessages => write!(f, "no log messages"),
+        }
+    }
 }
 
 // FIX: Added #[allow(dead_code)] to suppress unused field warning
@@ -38,47 +312,172 @@ pub struct LogMessage {
     slot: u64,
 }
 
-#[derive(Debug, Clone, BorshDeserialize)]
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, Serialize)]
 pub struct CampaignCreated {
     pub campaign_id: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
     pub creator_pubkey: Pubkey,
     pub goal_amount: u64,
     pub deadline: i64,
     pub metadata_url: String,
+    pub decimals: u8,
+    #[serde(serialize_with = "serialize_symbol")]
+    pub symbol: [u8; 8],
 }
 
-#[derive(Debug, Clone, BorshDeserialize)]
+/// `CampaignCreated` as emitted by the original program, before campaigns had
+/// a display currency
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct LegacyCampaignCreated {
+    pub campaign_id: u64,
+    pub creator_pubkey: Pubkey,
+    pub goal_amount: u64,
+    pub deadline: i64,
+    pub metadata_url: String,
+}
+
+impl From<LegacyCampaignCreated> for CampaignCreated {
+    /// Those campaigns all raised SOL, so they display as 9 decimals with no symbol
+    fn from(event: LegacyCampaignCreated) -> Self {
+        CampaignCreated {
+            campaign_id: event.campaign_id,
+            creator_pubkey: event.creator_pubkey,
+            goal_amount: event.goal_amount,
+            deadline: event.deadline,
+            metadata_url: event.metadata_url,
+            decimals: 9,
+            symbol: [0; 8],
+        }
+    }
+}
+
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, Serialize)]
 pub struct CampaignDonated {
+    pub campaign_id: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub donor_pubkey: Pubkey,
+    pub amount: u64,
+    /// 0 if the event predates it
+    pub goal_reached_at: i64,
+    /// Campaign total including this donation; 0 if the event predates it
+    pub amount_raised: u64,
+    /// 0 if the event predates it
+    pub goal_amount: u64,
+}
+
+/// `CampaignDonated` as emitted by the original program, before it carried
+/// `goal_reached_at` and the campaign's progress
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct LegacyCampaignDonated {
     pub campaign_id: u64,
     pub donor_pubkey: Pubkey,
     pub amount: u64,
 }
 
-#[derive(Debug, Clone, BorshDeserialize)]
+impl From<LegacyCampaignDonated> for CampaignDonated {
+    fn from(event: LegacyCampaignDonated) -> Self {
+        CampaignDonated {
+            campaign_id: event.campaign_id,
+            donor_pubkey: event.donor_pubkey,
+            amount: event.amount,
+            goal_reached_at: 0,
+            amount_raised: 0,
+            goal_amount: 0,
+        }
+    }
+}
+
+impl CampaignDonated {
+    /// The donor as stored in `campaign_events`, [`ANONYMOUS_DONOR`] if withheld
+    pub fn donor(&self) -> String {
+        if self.donor_pubkey == Pubkey::default() {
+            ANONYMOUS_DONOR.to_string()
+        } else {
+            self.donor_pubkey.to_string()
+        }
+    }
+}
+
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize, Serialize)]
 pub struct CampaignWithdrawn {
     pub campaign_id: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
     pub creator_pubkey: Pubkey,
     pub amount: u64,
+    #[serde(serialize_with = "serialize_pubkey")]
+    pub destination: Pubkey,
 }
 
-#[derive(Debug, Clone)]
+/// `CampaignWithdrawn` as emitted by the original program, which always paid
+/// out to the creator
+#[derive(Debug, Clone, BorshDeserialize, BorshSerialize)]
+pub struct LegacyCampaignWithdrawn {
+    pub campaign_id: u64,
+    pub creator_pubkey: Pubkey,
+    pub amount: u64,
+}
+
+impl From<LegacyCampaignWithdrawn> for CampaignWithdrawn {
+    fn from(event: LegacyCampaignWithdrawn) -> Self {
+        CampaignWithdrawn {
+            campaign_id: event.campaign_id,
+            creator_pubkey: event.creator_pubkey,
+            amount: event.amount,
+            destination: event.creator_pubkey,
+        }
+    }
+}
+
+/// Serialized with the same `event_type` names stored in `campaign_events`
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "event_type", rename_all = "lowercase")]
 pub enum CampaignEvent {
     Created(CampaignCreated),
     Donated(CampaignDonated),
     Withdrawn(CampaignWithdrawn),
 }
 
+impl CampaignEvent {
+    /// The `event_type` this event is stored under
+    pub fn event_type(&self) -> &'static str {
+        match self {
+            CampaignEvent::Created(_) => "created",
+            CampaignEvent::Donated(_) => "donated",
+            CampaignEvent::Withdrawn(_) => "withdrawn",
+        }
+    }
+}
+
 impl SolanaIndexer {
-    pub fn new(state: Arc<AppState>, program_id: String, ws_url: String) -> Self {
-        SolanaIndexer {
+    /// Fails with a readable error if `program_id` is not a pubkey or `ws_url`
+    /// is not a websocket URL, instead of panicking at startup
+    pub fn new(
+        state: Arc<AppState>,
+        program_id: String,
+        ws_url: String,
+        channel_buffer_size: usize,
+        index_event_types: IndexEventTypes,
+    ) -> Result<Self> {
+        let program_id = parse_program_id(&program_id)?;
+        if !(ws_url.starts_with("ws://") || ws_url.starts_with("wss://")) {
+            anyhow::bail!(
+                "invalid SOLANA_WS_URL '{}': expected a ws:// or wss:// URL",
+                ws_url
+            );
+        }
+
+        Ok(SolanaIndexer {
             state,
-            program_id: program_id.parse().unwrap(),
+            program_id,
             ws_url,
-        }
+            channel_buffer_size,
+            index_event_types,
+        })
     }
 
     pub async fn start(self: SolanaIndexer) -> Result<()> {
-        let (tx, mut rx) = mpsc::channel::<LogMessage>(CHANNEL_BUFFER_SIZE);
+        let (tx, rx) = mpsc::channel::<LogMessage>(self.channel_buffer_size);
+        let tx = PipelineSender::new(tx, self.state.pipeline_metrics.clone());
         let tx_clone = tx.clone();
         let ws_url_clone = self.ws_url.clone();
         let state_clone = self.state.clone();
@@ -100,11 +499,13 @@ impl SolanaIndexer {
         drop(tx);
 
         tokio::spawn(async move {
-            while let Some(log_msg) = rx.recv().await {
-                if let Err(e) = self_clone.process_log_message(log_msg).await {
-                    error!("Error processing log message: {:?}", e);
-                }
-            }
+            process_with_retries(
+                rx,
+                RetryPolicy::default(),
+                |log_msg| self_clone.process_log_message(log_msg),
+                |log_msg, attempts, e| self_clone.record_failed_indexing(log_msg, attempts, e),
+            )
+            .await;
         });
 
         Ok(())
@@ -113,17 +514,31 @@ impl SolanaIndexer {
     pub async fn subscribe_to_program_logs(
         ws_url: String,
         program_id: Pubkey,
-        tx: mpsc::Sender<LogMessage>,
-        _state: Arc<AppState>,
+        tx: PipelineSender,
+        state: Arc<AppState>,
     ) -> Result<()> {
+        let failures = &state.pipeline_metrics.subscription_failures;
+
         loop {
-            match Self::run_subscription(ws_url.clone(), program_id, tx.clone()).await {
+            match Self::run_subscription(ws_url.clone(), program_id, tx.clone(), state.clone())
+                .await
+            {
                 std::result::Result::Ok(_) => {
                     warn!("Subscription ended unexpectedly, reconnecting...");
                 }
                 std::result::Result::Err(e) => {
-                    error!("Subscription error: {:?}, reconnecting in 5s...", e);
-                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                    let attempt = u32::try_from(failures.fetch_add(1, Ordering::Relaxed) + 1)
+                        .unwrap_or(u32::MAX);
+                    let delay = with_jitter(reconnect_backoff(attempt));
+                    if attempt >= RECONNECT_ALERT_THRESHOLD {
+                        error!(
+                            "Subscription has failed {} times in a row: {:?}, reconnecting in {:?}",
+                            attempt, e, delay
+                        );
+                    } else {
+                        warn!("Subscription error: {:?}, reconnecting in {:?}", e, delay);
+                    }
+                    tokio::time::sleep(delay).await;
                 }
             }
         }
@@ -132,7 +547,8 @@ impl SolanaIndexer {
     async fn run_subscription(
         ws_url: String,
         program_id: Pubkey,
-        tx: mpsc::Sender<LogMessage>,
+        tx: PipelineSender,
+        state: Arc<AppState>,
     ) -> Result<()> {
         let program_id_str = program_id.to_string();
 
@@ -148,6 +564,18 @@ impl SolanaIndexer {
         .await
         .context("Failed to spawn subscription task")??;
 
+        // Connected: a later failure starts a fresh outage
+        state
+            .pipeline_metrics
+            .subscription_failures
+            .store(0, Ordering::Relaxed);
+
+        // Backfill only once the new subscription is live: anything landing from
+        // here on is buffered by the subscription, so the gap is fully covered.
+        if let Err(e) = Self::backfill_gap(&state, program_id, &tx).await {
+            error!("Backfill error: {:?}", e);
+        }
+
         loop {
             match subscription.1.recv() {
                 std::result::Result::Ok(response) => {
@@ -174,6 +602,91 @@ impl SolanaIndexer {
         std::result::Result::Ok(())
     }
 
+    /// Queues every program transaction from `last_indexed_slot` onwards, oldest
+    /// first, so transactions missed while disconnected still get indexed.
+    /// Signatures that were already stored are absorbed by the dedup constraints.
+    /// A fresh index (slot 0) is not backfilled.
+    async fn backfill_gap(
+        state: &Arc<AppState>,
+        program_id: Pubkey,
+        tx: &PipelineSender,
+    ) -> Result<usize> {
+        let last_slot = *state
+            .last_indexed_slot
+            .read()
+            .map_err(|e| anyhow::anyhow!("Failed to acquire read lock: {}", e))?;
+
+        if last_slot == 0 {
+            return Ok(0);
+        }
+
+        let rpc_client = state.rpc_client.clone();
+        let signatures = tokio::task::spawn_blocking(move || {
+            Self::fetch_signatures_since(&rpc_client, &program_id, last_slot)
+        })
+        .await
+        .context("Failed to spawn backfill task")??;
+
+        let count = signatures.len();
+        for status in signatures.into_iter().rev() {
+            let log_msg = LogMessage {
+                signature: status.signature,
+                program_id,
+                slot: status.slot,
+            };
+
+            tx.send(log_msg)
+                .await
+                .map_err(|_| anyhow::anyhow!("Processing channel closed during backfill"))?;
+        }
+
+        info!("Backfilled {} transactions from slot {}", count, last_slot);
+        Ok(count)
+    }
+
+    /// Pages back through the program's signatures (newest first) until it
+    /// reaches one older than `last_slot`.
+    fn fetch_signatures_since(
+        rpc_client: &RpcClient,
+        program_id: &Pubkey,
+        last_slot: u64,
+    ) -> Result<Vec<RpcConfirmedTransactionStatusWithSignature>> {
+        let mut signatures = Vec::new();
+        let mut before = None;
+
+        loop {
+            let page = rpc_client
+                .get_signatures_for_address_with_config(
+                    program_id,
+                    GetConfirmedSignaturesForAddress2Config {
+                        before,
+                        until: None,
+                        limit: Some(BACKFILL_PAGE_LIMIT),
+                        commitment: Some(CommitmentConfig::confirmed()),
+                    },
+                )
+                .context("Failed to fetch signatures for backfill")?;
+
+            let page_len = page.len();
+            // The last indexed slot may have been only partially processed, so
+            // it is re-queued rather than treated as done.
+            let reached_indexed = page.iter().any(|s| s.slot < last_slot);
+            before = page
+                .last()
+                .map(|s| s.signature.parse::<Signature>())
+                .transpose()
+                .context("Failed to parse backfill signature")?;
+
+            signatures.extend(page.into_iter().filter(|s| s.slot >= last_slot));
+
+            if reached_indexed || page_len < BACKFILL_PAGE_LIMIT {
+                break;
+            }
+        }
+
+        Ok(signatures)
+    }
+
     async fn process_log_message(&self, log_msg: LogMessage) -> Result<()> {
         info!("Processing tx: {} (slot: {})", log_msg.signature, log_msg.slot);
 
@@ -194,42 +707,165 @@ impl SolanaIndexer {
             .context("Failed to parse signature")?;
 
         let tx_with_meta = self
-            .state
-            .rpc_client
-            .get_transaction_with_config(
-                &signature,
-                RpcTransactionConfig {
-                    encoding: Some(UiTransactionEncoding::Json),
-                    commitment: Some(CommitmentConfig::confirmed()),
-                    max_supported_transaction_version: Some(0),
-                },
-            )
-            .context("Failed to fetch transaction")?;
+            .fetch_transaction(&signature, UiTransactionEncoding::Json)
+            .await?;
 
-        self.store_block(log_msg.slot, &tx_with_meta).await?;
+        self.index_transaction(&log_msg, &signature, &tx_with_meta)
+            .await
+    }
 
-        self.store_transaction(&log_msg.signature, log_msg.slot, &tx_with_meta)
+    /// Stores an already fetched transaction: its block, the transaction and the
+    /// SolRaiser events in its logs
+    async fn index_transaction(
+        &self,
+        log_msg: &LogMessage,
+        signature: &Signature,
+        tx_with_meta: &EncodedConfirmedTransactionWithStatusMeta,
+    ) -> Result<()> {
+        self.store_block(log_msg.slot, tx_with_meta).await?;
+
+        self.store_transaction(&log_msg.signature, log_msg.slot, tx_with_meta)
             .await?;
 
-        if let Some(meta) = tx_with_meta.transaction.meta.as_ref() {
-            let logs = match &meta.log_messages {
-                solana_transaction_status::option_serializer::OptionSerializer::Some(logs) => Some(logs),
-                solana_transaction_status::option_serializer::OptionSerializer::Skip => None,
-                solana_transaction_status::option_serializer::OptionSerializer::None => None,
-            };
-            
-            if let Some(logs) = logs {
-                if let Some(event) = Self::parse_anchor_event(logs) {
-                    self.store_campaign_event(&log_msg.signature, log_msg.slot, event)
-                        .await?;
+        let logs = match Self::transaction_logs(tx_with_meta) {
+            std::result::Result::Ok(logs) => Some(logs.clone()),
+            std::result::Result::Err(missing) => {
+                // Some nodes only strip logs from the raw encoding, so ask once more
+                warn!(
+                    "Transaction {} came back with {}, refetching as jsonParsed",
+                    log_msg.signature, missing
+                );
+                let parsed = self
+                    .fetch_transaction(signature, UiTransactionEncoding::JsonParsed)
+                    .await?;
+                match Self::transaction_logs(&parsed) {
+                    std::result::Result::Ok(logs) => Some(logs.clone()),
+                    std::result::Result::Err(missing) => {
+                        self.state
+                            .pipeline_metrics
+                            .transactions_without_logs
+                            .fetch_add(1, Ordering::Relaxed);
+                        warn!(
+                            "Transaction {} still has {}; its SolRaiser events are not indexed. \
+                             The RPC node may be stripping transaction logs.",
+                            log_msg.signature, missing
+                        );
+                        None
+                    }
                 }
             }
+        };
+
+        if let Some(logs) = logs {
+            self.store_log_events(&log_msg.signature, log_msg.slot, &logs)
+                .await?;
         }
 
         info!("Stored tx: {}", log_msg.signature);
         Ok(())
     }
 
+    /// Keeps a message that kept failing in `failed_indexing`, so it can be
+    /// reprocessed with the `reindex` command
+    async fn record_failed_indexing(
+        &self,
+        log_msg: LogMessage,
+        attempts: u32,
+        error: anyhow::Error,
+    ) -> Result<()> {
+        sqlx::query!(
+            r#"
+            INSERT INTO failed_indexing (signature, slot, attempts, last_error)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (signature) DO UPDATE
+            SET slot = EXCLUDED.slot,
+                attempts = failed_indexing.attempts + EXCLUDED.attempts,
+                last_error = EXCLUDED.last_error,
+                failed_at = CURRENT_TIMESTAMP
+            "#,
+            log_msg.signature,
+            log_msg.slot as i64,
+            attempts as i32,
+            format!("{:?}", error),
+        )
+        .execute(&self.state.db)
+        .await
+        .context("Failed to record failed indexing")?;
+
+        Ok(())
+    }
+
+    /// Fetches, parses and stores one transaction outside the live pipeline,
+    /// e.g. one the subscription missed or one in `failed_indexing`, whose row
+    /// is removed once it succeeds. Events already stored for the signature are
+    /// left as they are.
+    pub async fn reindex_signature(&self, signature: &str) -> Result<()> {
+        let parsed = signature
+            .parse::<Signature>()
+            .with_context(|| format!("Invalid signature '{}'", signature))?;
+        let tx_with_meta = self
+            .fetch_transaction(&parsed, UiTransactionEncoding::Json)
+            .await?;
+        let log_msg = LogMessage {
+            signature: signature.to_string(),
+            program_id: self.program_id,
+            slot: tx_with_meta.slot,
+        };
+
+        self.index_transaction(&log_msg, &parsed, &tx_with_meta)
+            .await?;
+
+        sqlx::query!(
+            "DELETE FROM failed_indexing WHERE signature = $1",
+            signature
+        )
+        .execute(&self.state.db)
+        .await
+        .context("Failed to clear failed indexing")?;
+
+        Ok(())
+    }
+
+    async fn fetch_transaction(
+        &self,
+        signature: &Signature,
+        encoding: UiTransactionEncoding,
+    ) -> Result<EncodedConfirmedTransactionWithStatusMeta> {
+        let rpc_client = self.state.rpc_client.clone();
+        let signature = *signature;
+        tokio::task::spawn_blocking(move || {
+            rpc_client.get_transaction_with_config(
+                &signature,
+                RpcTransactionConfig {
+                    encoding: Some(encoding),
+                    commitment: Some(CommitmentConfig::confirmed()),
+                    max_supported_transaction_version: Some(0),
+                },
+            )
+        })
+        .await
+        .context("Failed to spawn transaction lookup")?
+        .context("Failed to fetch transaction")
+    }
+
+    /// Program logs of a fetched transaction, or why the RPC left them out
+    fn transaction_logs(
+        tx_with_meta: &EncodedConfirmedTransactionWithStatusMeta,
+    ) -> std::result::Result<&Vec<String>, MissingLogs> {
+        let meta = tx_with_meta
+            .transaction
+            .meta
+            .as_ref()
+            .ok_or(MissingLogs::NoMeta)?;
+
+        match &meta.log_messages {
+            OptionSerializer::Some(logs) => std::result::Result::Ok(logs),
+            OptionSerializer::None | OptionSerializer::Skip => {
+                std::result::Result::Err(MissingLogs::NoLogMessages)
+            }
+        }
+    }
+
     async fn store_block(
         &self,
         slot: u64,
@@ -283,23 +919,31 @@ impl SolanaIndexer {
             .transaction
             .meta
             .as_ref()
-            .and_then(|m| Some(m.fee as i64));
+            .map(|m| m.fee as i64);
+
+        let error_detail = tx_with_meta
+            .transaction
+            .meta
+            .as_ref()
+            .and_then(Self::transaction_error_detail);
 
         sqlx::query!(
             r#"
-            INSERT INTO transactions (signature, slot, block_time, success, fee)
-            VALUES ($1, $2, $3, $4, $5)
+            INSERT INTO transactions (signature, slot, block_time, success, fee, error_detail)
+            VALUES ($1, $2, $3, $4, $5, $6)
             ON CONFLICT (signature) DO UPDATE
             SET slot = EXCLUDED.slot,
                 block_time = EXCLUDED.block_time,
                 success = EXCLUDED.success,
-                fee = EXCLUDED.fee
+                fee = EXCLUDED.fee,
+                error_detail = EXCLUDED.error_detail
             "#,
             signature,
             slot as i64,
             block_time,
             success,
             fee,
+            error_detail,
         )
         .execute(&self.state.db)
         .await
@@ -308,80 +952,206 @@ impl SolanaIndexer {
         Ok(())
     }
 
-    fn parse_anchor_event(logs: &[String]) -> Option<CampaignEvent> {
-        for log in logs {
-            if let Some(data_str) = log.strip_prefix(ANCHOR_EVENT_DISCRIMINATOR) {
-                if let Ok(data) = base64::engine::general_purpose::STANDARD.decode(data_str.trim()) {
-                    if data.len() < 8 {
-                        continue;
-                    }
+    /// For a failed transaction, keeps the error together with its program logs
+    /// so a revert (e.g. `GoalNotReached`) can be diagnosed without the RPC.
+    fn transaction_error_detail(meta: &UiTransactionStatusMeta) -> Option<serde_json::Value> {
+        let err = meta.err.as_ref()?;
+        let logs = match &meta.log_messages {
+            OptionSerializer::Some(logs) => logs.clone(),
+            OptionSerializer::None | OptionSerializer::Skip => Vec::new(),
+        };
 
-                    let _discriminator = &data[0..8];
-                    let event_data = &data[8..];
+        Some(serde_json::json!({ "err": err, "logs": logs }))
+    }
 
-                    if let Ok(event) = CampaignCreated::try_from_slice(event_data) {
-                        return Some(CampaignEvent::Created(event));
-                    }
+    /// Decodes every SolRaiser event in the transaction logs, in log order
+    pub fn parse_anchor_event(program_id: &Pubkey, logs: &[String]) -> Vec<CampaignEvent> {
+        Self::parse_anchor_event_with_logs(program_id, logs)
+            .into_iter()
+            .map(|(event, _)| event)
+            .collect()
+    }
 
-                    if let Ok(event) = CampaignDonated::try_from_slice(event_data) {
-                        return Some(CampaignEvent::Donated(event));
-                    }
+    /// Like [`Self::parse_anchor_event`], pairing each event with the log lines of
+    /// the top-level instruction that emitted it: its `Program log:` lines so far
+    /// and the event's own `Program data:` line.
+    ///
+    /// Only lines logged while `program_id` is the innermost invoked program are
+    /// read, so another program in the transaction (e.g. one calling SolRaiser
+    /// through CPI) can't pass off its own `Program data:` as a SolRaiser event.
+    pub fn parse_anchor_event_with_logs(
+        program_id: &Pubkey,
+        logs: &[String],
+    ) -> Vec<(CampaignEvent, Vec<String>)> {
+        let program_id = program_id.to_string();
+        let mut events = Vec::new();
+        let mut instruction_logs: Vec<String> = Vec::new();
+        let mut invoke_stack: Vec<&str> = Vec::new();
 
-                    if let Ok(event) = CampaignWithdrawn::try_from_slice(event_data) {
-                        return Some(CampaignEvent::Withdrawn(event));
-                    }
+        for log in logs {
+            let in_program = invoke_stack.last() == Some(&program_id.as_str());
+            if log.starts_with(PROGRAM_LOG_PREFIX) {
+                if in_program {
+                    instruction_logs.push(log.clone());
+                }
+            } else if log.starts_with(ANCHOR_EVENT_DISCRIMINATOR) {
+                if let Some(event) = Self::decode_event_line(log).filter(|_| in_program) {
+                    let mut raw_logs = instruction_logs.clone();
+                    raw_logs.push(log.clone());
+                    events.push((event, raw_logs));
+                }
+            } else if let Some(invoked) = invoked_program(log) {
+                if invoke_stack.is_empty() {
+                    instruction_logs.clear();
                 }
+                invoke_stack.push(invoked);
+            } else if is_program_return(log) {
+                invoke_stack.pop();
             }
         }
-        None
+        events
+    }
+
+    /// Decodes one `Program data:` line, if its discriminator is a SolRaiser event's.
+    /// Events in the layouts of older program versions are upgraded; a SolRaiser
+    /// event in no known layout is logged and skipped.
+    fn decode_event_line(log: &str) -> Option<CampaignEvent> {
+        let data_str = log.strip_prefix(ANCHOR_EVENT_DISCRIMINATOR)?;
+        let data = base64::engine::general_purpose::STANDARD
+            .decode(data_str.trim())
+            .ok()?;
+        if data.len() < 8 {
+            return None;
+        }
+
+        let (discriminator, event_data) = data.split_at(8);
+
+        let event = if discriminator == event_discriminator("CampaignCreated") {
+            CampaignCreated::try_from_slice(event_data)
+                .or_else(|_| LegacyCampaignCreated::try_from_slice(event_data).map(Into::into))
+                .map(CampaignEvent::Created)
+        } else if discriminator == event_discriminator("CampaignDonated") {
+            CampaignDonated::try_from_slice(event_data)
+                .or_else(|_| LegacyCampaignDonated::try_from_slice(event_data).map(Into::into))
+                .map(CampaignEvent::Donated)
+        } else if discriminator == event_discriminator("CampaignWithdrawn") {
+            CampaignWithdrawn::try_from_slice(event_data)
+                .or_else(|_| LegacyCampaignWithdrawn::try_from_slice(event_data).map(Into::into))
+                .map(CampaignEvent::Withdrawn)
+        } else {
+            return None;
+        };
+
+        match event {
+            std::result::Result::Ok(event) => Some(event),
+            std::result::Result::Err(e) => {
+                warn!(
+                    "Skipping a SolRaiser event of {} bytes in an unknown layout: {}",
+                    event_data.len(),
+                    e
+                );
+                None
+            }
+        }
+    }
+
+    async fn store_log_events(&self, signature: &str, slot: u64, logs: &[String]) -> Result<()> {
+        let events = Self::parse_anchor_event_with_logs(&self.program_id, logs);
+        for (event_index, (event, raw_logs)) in events.into_iter().enumerate() {
+            self.store_campaign_event(signature, slot, event_index as i32, event, &raw_logs)
+                .await?;
+        }
+
+        Ok(())
     }
 
     async fn store_campaign_event(
         &self,
         signature: &str,
         slot: u64,
+        event_index: i32,
         event: CampaignEvent,
+        raw_logs: &[String],
     ) -> Result<()> {
+        if !self.index_event_types.includes(event.event_type()) {
+            debug!(
+                "Skipping {} event in {}: not in INDEX_EVENT_TYPES",
+                event.event_type(),
+                signature
+            );
+            return Ok(());
+        }
+
         match event {
             CampaignEvent::Created(e) => {
                 sqlx::query!(
                     r#"
                     INSERT INTO campaign_events 
-                    (signature, slot, event_type, campaign_id, user_pubkey, amount, goal_amount, deadline, metadata_url)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                    (signature, slot, event_index, event_type, campaign_id, user_pubkey, amount, goal_amount, deadline, metadata_url, decimals, symbol, raw_logs)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                    ON CONFLICT (signature, event_index) DO NOTHING
                     "#,
                     signature,
                     slot as i64,
+                    event_index,
                     "created",
                     e.campaign_id as i64,
                     e.creator_pubkey.to_string(),
                     None::<i64>,
                     Some(e.goal_amount as i64),
                     Some(e.deadline),
-                    Some(e.metadata_url),
+                    Some(e.metadata_url.clone()),
+                    Some(e.decimals as i16),
+                    symbol_to_string(&e.symbol),
+                    raw_logs,
                 )
                 .execute(&self.state.db)
                 .await
                 .context("Failed to insert CampaignCreated event")?;
 
                 info!("Stored CampaignCreated event: campaign_id={}", e.campaign_id);
+
+                // Resolving off-chain metadata is best-effort and must not hold up indexing
+                let db = self.state.db.clone();
+                let fetcher = self.state.metadata_fetcher.clone();
+                tokio::spawn(async move {
+                    if let Err(err) = metadata::cache_campaign_metadata(
+                        &fetcher,
+                        &db,
+                        e.campaign_id as i64,
+                        &e.metadata_url,
+                    )
+                    .await
+                    {
+                        warn!(
+                            "Failed to cache metadata for campaign {}: {:?}",
+                            e.campaign_id, err
+                        );
+                    }
+                });
             }
             CampaignEvent::Donated(e) => {
                 sqlx::query!(
                     r#"
                     INSERT INTO campaign_events 
-                    (signature, slot, event_type, campaign_id, user_pubkey, amount, goal_amount, deadline, metadata_url)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                    (signature, slot, event_index, event_type, campaign_id, user_pubkey, amount, goal_amount, deadline, metadata_url, goal_reached_at, amount_raised, raw_logs)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+                    ON CONFLICT (signature, event_index) DO NOTHING
                     "#,
                     signature,
                     slot as i64,
+                    event_index,
                     "donated",
                     e.campaign_id as i64,
-                    e.donor_pubkey.to_string(),
+                    e.donor(),
                     Some(e.amount as i64),
-                    None::<i64>,
+                    // A goal is never 0, so 0 marks an event without progress
+                    (e.goal_amount != 0).then_some(e.goal_amount as i64),
                     None::<i64>,
                     None::<String>,
+                    (e.goal_reached_at != 0).then_some(e.goal_reached_at),
+                    (e.goal_amount != 0).then_some(e.amount_raised as i64),
+                    raw_logs,
                 )
                 .execute(&self.state.db)
                 .await
@@ -396,11 +1166,13 @@ impl SolanaIndexer {
                 sqlx::query!(
                     r#"
                     INSERT INTO campaign_events 
-                    (signature, slot, event_type, campaign_id, user_pubkey, amount, goal_amount, deadline, metadata_url)
-                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9)
+                    (signature, slot, event_index, event_type, campaign_id, user_pubkey, amount, goal_amount, deadline, metadata_url, raw_logs)
+                    VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                    ON CONFLICT (signature, event_index) DO NOTHING
                     "#,
                     signature,
                     slot as i64,
+                    event_index,
                     "withdrawn",
                     e.campaign_id as i64,
                     e.creator_pubkey.to_string(),
@@ -408,6 +1180,7 @@ impl SolanaIndexer {
                     None::<i64>,
                     None::<i64>,
                     None::<String>,
+                    raw_logs,
                 )
                 .execute(&self.state.db)
                 .await
@@ -423,3 +1196,784 @@ impl SolanaIndexer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use solana_client::{rpc_client::Mocks, rpc_request::RpcRequest};
+    use sqlx::postgres::PgPoolOptions;
+    use std::sync::RwLock;
+
+    fn mock_state(mocks: Mocks, last_indexed_slot: u64) -> Arc<AppState> {
+        Arc::new(AppState {
+            db: PgPoolOptions::new()
+                .connect_lazy("postgres://localhost/unused")
+                .unwrap(),
+            rpc_client: Arc::new(RpcClient::new_mock_with_mocks("succeeds", mocks)),
+            program_id: Pubkey::new_unique(),
+            last_indexed_slot: Arc::new(RwLock::new(last_indexed_slot)),
+            pipeline_metrics: Arc::default(),
+            idempotency: Arc::default(),
+            campaign_rent: Arc::default(),
+            creator_stats_rent: Arc::default(),
+            rpc_rate_limiter: Arc::default(),
+            metadata_fetcher: Arc::default(),
+            public_url: None,
+        })
+    }
+
+    fn signature_status(signature: &Signature, slot: u64) -> serde_json::Value {
+        serde_json::json!({
+            "signature": signature.to_string(),
+            "slot": slot,
+            "err": null,
+            "memo": null,
+            "blockTime": null,
+            "confirmationStatus": "confirmed",
+        })
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn backfill_queues_gap_transactions_oldest_first() {
+        let program_id = Pubkey::new_unique();
+        let sigs: Vec<Signature> = (0..4).map(|_| Signature::new_unique()).collect();
+
+        // Indexed up to slot 100 before the drop; 103 and 105 landed in the gap
+        let page = serde_json::json!([
+            signature_status(&sigs[0], 105),
+            signature_status(&sigs[1], 103),
+            signature_status(&sigs[2], 100),
+            signature_status(&sigs[3], 97),
+        ]);
+        let mocks = Mocks::from([(RpcRequest::GetSignaturesForAddress, page)]);
+        let state = mock_state(mocks, 100);
+
+        let (tx, mut rx) = mpsc::channel(16);
+        let tx = PipelineSender::new(tx, Arc::default());
+        let count = SolanaIndexer::backfill_gap(&state, program_id, &tx)
+            .await
+            .unwrap();
+        drop(tx);
+
+        let mut queued = Vec::new();
+        while let Some(msg) = rx.recv().await {
+            queued.push((msg.signature, msg.slot));
+        }
+
+        assert_eq!(count, 3);
+        assert_eq!(
+            queued,
+            vec![
+                (sigs[2].to_string(), 100),
+                (sigs[1].to_string(), 103),
+                (sigs[0].to_string(), 105),
+            ]
+        );
+    }
+
+    fn status_meta(err: serde_json::Value, logs: &[&str]) -> UiTransactionStatusMeta {
+        serde_json::from_value(serde_json::json!({
+            "err": err,
+            "status": if err.is_null() {
+                serde_json::json!({ "Ok": null })
+            } else {
+                serde_json::json!({ "Err": err })
+            },
+            "fee": 5000,
+            "preBalances": [],
+            "postBalances": [],
+            "logMessages": logs,
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn error_detail_captures_err_and_logs_for_failed_tx() {
+        let err = serde_json::json!({ "InstructionError": [0, { "Custom": 6008 }] });
+        let logs = [
+            "Program 62NbBCCxPfR83xtgw3AaxKGHyyDdxobrcCGzA7s7LFie invoke [1]",
+            "Program log: AnchorError occurred. Error Code: GoalNotReached.",
+        ];
+
+        let detail =
+            SolanaIndexer::transaction_error_detail(&status_meta(err.clone(), &logs)).unwrap();
+
+        assert_eq!(detail["err"], err);
+        assert_eq!(detail["logs"], serde_json::json!(logs));
+    }
+
+    #[test]
+    fn error_detail_is_none_for_successful_tx() {
+        let meta = status_meta(serde_json::Value::Null, &["Program log: ok"]);
+        assert!(SolanaIndexer::transaction_error_detail(&meta).is_none());
+    }
+
+    const TEST_PROGRAM_ID: &str = "62NbBCCxPfR83xtgw3AaxKGHyyDdxobrcCGzA7s7LFie";
+
+    fn program_data_line<T: BorshSerialize>(name: &str, event: &T) -> String {
+        let mut data = event_discriminator(name).to_vec();
+        data.extend(borsh::to_vec(event).unwrap());
+        format!(
+            "{}{}",
+            ANCHOR_EVENT_DISCRIMINATOR,
+            base64::engine::general_purpose::STANDARD.encode(data)
+        )
+    }
+
+    /// `lines` logged by a top-level invocation of `program`
+    fn invocation(program: &str, lines: Vec<String>) -> Vec<String> {
+        let mut logs = vec![format!("Program {} invoke [1]", program)];
+        logs.extend(lines);
+        logs.push(format!("Program {} success", program));
+        logs
+    }
+
+    fn created_and_donated_logs(campaign_id: u64) -> Vec<String> {
+        vec![
+            format!("Program {} invoke [1]", TEST_PROGRAM_ID),
+            program_data_line(
+                "CampaignCreated",
+                &CampaignCreated {
+                    campaign_id,
+                    creator_pubkey: Pubkey::new_unique(),
+                    goal_amount: 1_000_000_000,
+                    deadline: 1_700_000_000,
+                    metadata_url: "https://example.com/campaign.json".to_string(),
+                    decimals: 6,
+                    symbol: *b"USDC\0\0\0\0",
+                },
+            ),
+            "Program log: Instruction: Donate".to_string(),
+            program_data_line(
+                "CampaignDonated",
+                &CampaignDonated {
+                    campaign_id,
+                    donor_pubkey: Pubkey::new_unique(),
+                    amount: 250_000_000,
+                    goal_reached_at: 0,
+                    amount_raised: 400_000_000,
+                    goal_amount: 1_000_000_000,
+                },
+            ),
+        ]
+    }
+
+    #[test]
+    fn zeroed_donor_is_stored_as_anonymous() {
+        let mut donated = CampaignDonated {
+            campaign_id: 1,
+            donor_pubkey: Pubkey::default(),
+            amount: 100,
+            goal_reached_at: 0,
+            amount_raised: 100,
+            goal_amount: 1_000,
+        };
+        assert_eq!(donated.donor(), ANONYMOUS_DONOR);
+
+        donated.donor_pubkey = Pubkey::new_unique();
+        assert_eq!(donated.donor(), donated.donor_pubkey.to_string());
+    }
+
+    #[test]
+    fn parse_anchor_event_returns_all_events_in_log_order() {
+        let events = SolanaIndexer::parse_anchor_event(
+            &TEST_PROGRAM_ID.parse().unwrap(),
+            &created_and_donated_logs(7),
+        );
+
+        assert_eq!(events.len(), 2);
+        assert!(matches!(&events[0], CampaignEvent::Created(e) if e.campaign_id == 7));
+        assert!(matches!(&events[1], CampaignEvent::Donated(e) if e.amount == 250_000_000));
+    }
+
+    #[test]
+    fn donated_events_from_the_original_program_still_decode() {
+        // campaign_id, donor_pubkey, amount: the original 48-byte layout
+        let donor = Pubkey::new_unique();
+        let mut payload = 7u64.to_le_bytes().to_vec();
+        payload.extend(donor.to_bytes());
+        payload.extend(250_000_000u64.to_le_bytes());
+        assert_eq!(payload.len(), 48);
+
+        let mut data = event_discriminator("CampaignDonated").to_vec();
+        data.extend(payload);
+        let logs = invocation(
+            TEST_PROGRAM_ID,
+            vec![format!(
+                "{}{}",
+                ANCHOR_EVENT_DISCRIMINATOR,
+                base64::engine::general_purpose::STANDARD.encode(data)
+            )],
+        );
+        let events = SolanaIndexer::parse_anchor_event(&TEST_PROGRAM_ID.parse().unwrap(), &logs);
+
+        assert_eq!(events.len(), 1);
+        let CampaignEvent::Donated(donated) = &events[0] else {
+            panic!("expected a donated event, got {:?}", events[0]);
+        };
+        assert_eq!(donated.campaign_id, 7);
+        assert_eq!(donated.donor_pubkey, donor);
+        assert_eq!(donated.amount, 250_000_000);
+        assert_eq!(donated.goal_reached_at, 0);
+        assert_eq!((donated.amount_raised, donated.goal_amount), (0, 0));
+    }
+
+    #[test]
+    fn created_events_from_the_original_program_still_decode() {
+        let creator = Pubkey::new_unique();
+        let legacy = LegacyCampaignCreated {
+            campaign_id: 7,
+            creator_pubkey: creator,
+            goal_amount: 1_000_000_000,
+            deadline: 1_700_000_000,
+            metadata_url: "https://example.com/7.json".to_string(),
+        };
+
+        let logs = invocation(
+            TEST_PROGRAM_ID,
+            vec![program_data_line("CampaignCreated", &legacy)],
+        );
+        let events = SolanaIndexer::parse_anchor_event(&TEST_PROGRAM_ID.parse().unwrap(), &logs);
+
+        assert_eq!(events.len(), 1);
+        let CampaignEvent::Created(created) = &events[0] else {
+            panic!("expected a created event, got {:?}", events[0]);
+        };
+        assert_eq!(created.creator_pubkey, creator);
+        assert_eq!(created.metadata_url, "https://example.com/7.json");
+        assert_eq!(created.decimals, 9);
+        assert_eq!(symbol_to_string(&created.symbol), None);
+    }
+
+    #[test]
+    fn withdrawn_events_from_the_original_program_pay_the_creator() {
+        let creator = Pubkey::new_unique();
+        let legacy = LegacyCampaignWithdrawn {
+            campaign_id: 7,
+            creator_pubkey: creator,
+            amount: 1_500_000_000,
+        };
+
+        let logs = invocation(
+            TEST_PROGRAM_ID,
+            vec![program_data_line("CampaignWithdrawn", &legacy)],
+        );
+        let events = SolanaIndexer::parse_anchor_event(&TEST_PROGRAM_ID.parse().unwrap(), &logs);
+
+        assert_eq!(events.len(), 1);
+        let CampaignEvent::Withdrawn(withdrawn) = &events[0] else {
+            panic!("expected a withdrawn event, got {:?}", events[0]);
+        };
+        assert_eq!(withdrawn.amount, 1_500_000_000);
+        assert_eq!(withdrawn.destination, creator);
+    }
+
+    #[test]
+    fn foreign_program_donation_data_is_ignored() {
+        let foreign = Pubkey::new_unique().to_string();
+        let forged = program_data_line(
+            "CampaignDonated",
+            &CampaignDonated {
+                campaign_id: 7,
+                donor_pubkey: Pubkey::new_unique(),
+                amount: 1_000_000_000_000,
+                goal_reached_at: 0,
+                amount_raised: 1_000_000_000_000,
+                goal_amount: 1_000_000_000,
+            },
+        );
+        let created = created_and_donated_logs(7).swap_remove(1);
+
+        // A foreign top-level instruction, then a SolRaiser instruction whose
+        // CPI into the foreign program logs the same data
+        let mut logs = invocation(&foreign, vec![forged.clone()]);
+        logs.extend([
+            format!("Program {} invoke [1]", TEST_PROGRAM_ID),
+            format!("Program {} invoke [2]", foreign),
+            forged,
+            format!("Program {} success", foreign),
+            created,
+            format!("Program {} success", TEST_PROGRAM_ID),
+        ]);
+
+        let events = SolanaIndexer::parse_anchor_event(&TEST_PROGRAM_ID.parse().unwrap(), &logs);
+
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], CampaignEvent::Created(e) if e.campaign_id == 7));
+    }
+
+    #[test]
+    fn event_with_unknown_discriminator_is_ignored() {
+        let mut data = vec![0u8; 8];
+        data.extend(
+            borsh::to_vec(&CampaignWithdrawn {
+                campaign_id: 7,
+                creator_pubkey: Pubkey::new_unique(),
+                amount: 1_000,
+                destination: Pubkey::new_unique(),
+            })
+            .unwrap(),
+        );
+        let line = format!(
+            "{}{}",
+            ANCHOR_EVENT_DISCRIMINATOR,
+            base64::engine::general_purpose::STANDARD.encode(data)
+        );
+
+        let logs = invocation(TEST_PROGRAM_ID, vec![line]);
+        assert!(
+            SolanaIndexer::parse_anchor_event(&TEST_PROGRAM_ID.parse().unwrap(), &logs).is_empty()
+        );
+    }
+
+    #[sqlx::test]
+    async fn store_log_events_stores_every_event(pool: sqlx::PgPool) {
+        sqlx::query("INSERT INTO blocks (slot, blockhash) VALUES (50, 'hash')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO transactions (signature, slot, success) VALUES ('multi-sig', 50, true)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let state = Arc::new(AppState::new(
+            pool.clone(),
+            "http://127.0.0.1:8899".into(),
+            Pubkey::new_unique(),
+            0,
+        ));
+        let indexer = SolanaIndexer::new(
+            state,
+            TEST_PROGRAM_ID.to_string(),
+            "ws://127.0.0.1:8900".to_string(),
+            16,
+            IndexEventTypes::default(),
+        )
+        .unwrap();
+
+        let logs = created_and_donated_logs(7);
+        indexer
+            .store_log_events("multi-sig", 50, &logs)
+            .await
+            .unwrap();
+        // Redelivery of the same transaction is absorbed by the dedup constraint
+        indexer
+            .store_log_events("multi-sig", 50, &logs)
+            .await
+            .unwrap();
+
+        let stored: Vec<(i32, String)> = sqlx::query_as(
+            "SELECT event_index, event_type FROM campaign_events
+             WHERE signature = 'multi-sig' ORDER BY event_index",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+
+        assert_eq!(
+            stored,
+            vec![(0, "created".to_string()), (1, "donated".to_string())]
+        );
+
+        let display: (Option<i16>, Option<String>) = sqlx::query_as(
+            "SELECT decimals, symbol FROM campaign_events
+             WHERE signature = 'multi-sig' AND event_type = 'created'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(display, (Some(6), Some("USDC".to_string())));
+
+        let progress: (Option<i64>, Option<i64>) = sqlx::query_as(
+            "SELECT amount_raised, goal_amount FROM campaign_events
+             WHERE signature = 'multi-sig' AND event_type = 'donated'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(progress, (Some(400_000_000), Some(1_000_000_000)));
+
+        let raw_logs: Vec<Option<Vec<String>>> = sqlx::query_scalar(
+            "SELECT raw_logs FROM campaign_events
+             WHERE signature = 'multi-sig' ORDER BY event_index",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            raw_logs,
+            vec![
+                Some(vec![logs[1].clone()]),
+                Some(vec![logs[2].clone(), logs[3].clone()]),
+            ]
+        );
+    }
+
+    fn log_message(signature: &str) -> LogMessage {
+        LogMessage {
+            signature: signature.to_string(),
+            program_id: Pubkey::new_unique(),
+            slot: 70,
+        }
+    }
+
+    const FAST_RETRIES: RetryPolicy = RetryPolicy {
+        max_attempts: 3,
+        base_delay: Duration::from_millis(10),
+    };
+
+    #[tokio::test]
+    async fn failed_message_is_retried_until_processed() {
+        let (tx, rx) = mpsc::channel(4);
+        tx.send(log_message("flaky-sig")).await.unwrap();
+        tx.send(log_message("ok-sig")).await.unwrap();
+        drop(tx);
+
+        let attempts = AtomicUsize::new(0);
+        let processed = std::sync::Mutex::new(Vec::new());
+        let dead_lettered = AtomicUsize::new(0);
+
+        process_with_retries(
+            rx,
+            FAST_RETRIES,
+            |log_msg| {
+                // flaky-sig fails on its first attempt only
+                let succeeds =
+                    log_msg.signature != "flaky-sig" || attempts.fetch_add(1, Ordering::SeqCst) > 0;
+                if succeeds {
+                    processed.lock().unwrap().push(log_msg.signature);
+                }
+                async move {
+                    if succeeds {
+                        std::result::Result::Ok(())
+                    } else {
+                        Err(anyhow::anyhow!("connection reset"))
+                    }
+                }
+            },
+            |_, _, _| {
+                dead_lettered.fetch_add(1, Ordering::SeqCst);
+                async { std::result::Result::Ok(()) }
+            },
+        )
+        .await;
+
+        // The retry waited behind the message queued after it
+        assert_eq!(
+            *processed.lock().unwrap(),
+            vec!["ok-sig".to_string(), "flaky-sig".to_string()]
+        );
+        assert_eq!(dead_lettered.load(Ordering::SeqCst), 0);
+    }
+
+    #[sqlx::test]
+    async fn message_failing_every_attempt_is_recorded(pool: sqlx::PgPool) {
+        let state = Arc::new(AppState::new(
+            pool.clone(),
+            "http://127.0.0.1:8899".into(),
+            Pubkey::new_unique(),
+            0,
+        ));
+        let indexer = SolanaIndexer::new(
+            state,
+            Pubkey::new_unique().to_string(),
+            "ws://127.0.0.1:8900".to_string(),
+            16,
+            IndexEventTypes::default(),
+        )
+        .unwrap();
+
+        let (tx, rx) = mpsc::channel(1);
+        tx.send(log_message("doomed-sig")).await.unwrap();
+        drop(tx);
+
+        let attempts = AtomicUsize::new(0);
+        process_with_retries(
+            rx,
+            FAST_RETRIES,
+            |_| {
+                attempts.fetch_add(1, Ordering::SeqCst);
+                async { Err(anyhow::anyhow!("database unavailable")) }
+            },
+            |log_msg, attempts, e| indexer.record_failed_indexing(log_msg, attempts, e),
+        )
+        .await;
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+
+        let (slot, recorded_attempts, last_error): (i64, i32, String) = sqlx::query_as(
+            "SELECT slot, attempts, last_error FROM failed_indexing WHERE signature = 'doomed-sig'",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!((slot, recorded_attempts), (70, 3));
+        assert!(last_error.contains("database unavailable"));
+    }
+
+    #[test]
+    fn retry_delay_doubles() {
+        let policy = RetryPolicy::default();
+        assert_eq!(policy.delay(1), RETRY_BASE_DELAY);
+        assert_eq!(policy.delay(3), RETRY_BASE_DELAY * 4);
+    }
+
+    #[sqlx::test]
+    async fn unselected_event_types_are_skipped(pool: sqlx::PgPool) {
+        sqlx::query("INSERT INTO blocks (slot, blockhash) VALUES (51, 'hash')")
+            .execute(&pool)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO transactions (signature, slot, success) VALUES ('split-sig', 51, true)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let state = Arc::new(AppState::new(
+            pool.clone(),
+            "http://127.0.0.1:8899".into(),
+            Pubkey::new_unique(),
+            0,
+        ));
+        let indexer = SolanaIndexer::new(
+            state,
+            TEST_PROGRAM_ID.to_string(),
+            "ws://127.0.0.1:8900".to_string(),
+            16,
+            IndexEventTypes::parse("donated").unwrap(),
+        )
+        .unwrap();
+
+        let donated = CampaignDonated {
+            campaign_id: 8,
+            donor_pubkey: Pubkey::new_unique(),
+            amount: 250_000_000,
+            goal_reached_at: 0,
+            amount_raised: 250_000_000,
+            goal_amount: 200_000_000,
+        };
+        let withdrawn = CampaignWithdrawn {
+            campaign_id: 8,
+            creator_pubkey: Pubkey::new_unique(),
+            amount: 240_000_000,
+            destination: Pubkey::new_unique(),
+        };
+        let logs = invocation(
+            TEST_PROGRAM_ID,
+            vec![
+                program_data_line("CampaignDonated", &donated),
+                program_data_line("CampaignWithdrawn", &withdrawn),
+            ],
+        );
+        indexer
+            .store_log_events("split-sig", 51, &logs)
+            .await
+            .unwrap();
+
+        let stored: Vec<String> = sqlx::query_scalar(
+            "SELECT event_type FROM campaign_events WHERE signature = 'split-sig'",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(stored, vec!["donated".to_string()]);
+    }
+
+    fn transaction_response(signature: &Signature, meta: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "slot": 60,
+            "blockTime": 1_700_000_060,
+            "transaction": {
+                "signatures": [signature.to_string()],
+                "message": {
+                    "header": {
+                        "numRequiredSignatures": 1,
+                        "numReadonlySignedAccounts": 0,
+                        "numReadonlyUnsignedAccounts": 0,
+                    },
+                    "accountKeys": [Pubkey::new_unique().to_string()],
+                    "recentBlockhash": "11111111111111111111111111111111",
+                    "instructions": [],
+                },
+            },
+            "meta": meta,
+        })
+    }
+
+    #[test]
+    fn transaction_logs_reports_what_is_missing() {
+        let signature = Signature::new_unique();
+        let parse = |meta| {
+            serde_json::from_value::<EncodedConfirmedTransactionWithStatusMeta>(
+                transaction_response(&signature, meta),
+            )
+            .unwrap()
+        };
+
+        let no_meta = parse(serde_json::Value::Null);
+        assert_eq!(
+            SolanaIndexer::transaction_logs(&no_meta),
+            Err(MissingLogs::NoMeta)
+        );
+
+        let mut meta = serde_json::to_value(status_meta(serde_json::Value::Null, &[])).unwrap();
+        meta["logMessages"] = serde_json::Value::Null;
+        assert_eq!(
+            SolanaIndexer::transaction_logs(&parse(meta)),
+            Err(MissingLogs::NoLogMessages)
+        );
+
+        let with_logs = parse(
+            serde_json::to_value(status_meta(serde_json::Value::Null, &["Program log: ok"]))
+                .unwrap(),
+        );
+        assert_eq!(
+            SolanaIndexer::transaction_logs(&with_logs),
+            Ok(&vec!["Program log: ok".to_string()])
+        );
+    }
+
+    #[sqlx::test]
+    async fn metaless_transaction_is_stored_and_counted(pool: sqlx::PgPool) {
+        let signature = Signature::new_unique();
+        let mocks = Mocks::from([(
+            RpcRequest::GetTransaction,
+            transaction_response(&signature, serde_json::Value::Null),
+        )]);
+        let state = Arc::new(AppState {
+            db: pool.clone(),
+            rpc_client: Arc::new(RpcClient::new_mock_with_mocks("succeeds", mocks)),
+            program_id: Pubkey::new_unique(),
+            last_indexed_slot: Arc::new(RwLock::new(0)),
+            pipeline_metrics: Arc::default(),
+            idempotency: Arc::default(),
+            campaign_rent: Arc::default(),
+            creator_stats_rent: Arc::default(),
+            rpc_rate_limiter: Arc::default(),
+            metadata_fetcher: Arc::default(),
+            public_url: None,
+        });
+        let indexer = SolanaIndexer::new(
+            state.clone(),
+            Pubkey::new_unique().to_string(),
+            "ws://127.0.0.1:8900".to_string(),
+            16,
+            IndexEventTypes::default(),
+        )
+        .unwrap();
+
+        indexer
+            .process_log_message(LogMessage {
+                signature: signature.to_string(),
+                program_id: Pubkey::new_unique(),
+                slot: 60,
+            })
+            .await
+            .unwrap();
+
+        let stored: i64 =
+            sqlx::query_scalar("SELECT COUNT(*) FROM transactions WHERE signature = $1")
+                .bind(signature.to_string())
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(stored, 1);
+        assert_eq!(
+            state
+                .pipeline_metrics
+                .transactions_without_logs
+                .load(Ordering::Relaxed),
+            1
+        );
+    }
+
+    #[test]
+    fn reconnect_backoff_doubles_then_caps() {
+        let delays: Vec<u64> = (1..=8).map(|n| reconnect_backoff(n).as_secs()).collect();
+        assert_eq!(delays, vec![1, 2, 4, 8, 16, 32, 60, 60]);
+        assert_eq!(reconnect_backoff(u32::MAX), RECONNECT_MAX_DELAY);
+
+        let jittered = with_jitter(Duration::from_secs(10));
+        assert!(jittered >= Duration::from_secs(5) && jittered <= Duration::from_secs(10));
+    }
+
+    #[test]
+    fn symbol_padding_is_trimmed() {
+        assert_eq!(symbol_to_string(b"SOL\0\0\0\0\0").as_deref(), Some("SOL"));
+        assert_eq!(symbol_to_string(b"ABCDEFGH").as_deref(), Some("ABCDEFGH"));
+        assert_eq!(symbol_to_string(&[0; 8]), None);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn backfill_skips_fresh_index() {
+        let state = mock_state(Mocks::default(), 0);
+        let (tx, mut rx) = mpsc::channel(16);
+        let tx = PipelineSender::new(tx, Arc::default());
+
+        let count = SolanaIndexer::backfill_gap(&state, Pubkey::new_unique(), &tx)
+            .await
+            .unwrap();
+        drop(tx);
+
+        assert_eq!(count, 0);
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn pipeline_sender_counts_sends_into_saturated_channel() {
+        let metrics = Arc::new(PipelineMetrics::default());
+        let (tx, _rx) = mpsc::channel(10);
+        let tx = PipelineSender::new(tx, metrics.clone());
+
+        let log_msg = LogMessage {
+            signature: Signature::new_unique().to_string(),
+            program_id: Pubkey::new_unique(),
+            slot: 1,
+        };
+
+        // Depths 0..=7 are below 80% of the buffer
+        for _ in 0..8 {
+            tx.send(log_msg.clone()).await.unwrap();
+        }
+        assert_eq!(metrics.saturated_sends.load(Ordering::Relaxed), 0);
+
+        tx.send(log_msg.clone()).await.unwrap();
+        tx.send(log_msg).await.unwrap();
+        assert_eq!(metrics.saturated_sends.load(Ordering::Relaxed), 2);
+        assert_eq!(metrics.max_queue_depth.load(Ordering::Relaxed), 9);
+        assert!(tx.saturated.load(Ordering::Relaxed));
+    }
+
+    #[tokio::test]
+    async fn new_rejects_malformed_program_id_and_ws_url() {
+        let state = mock_state(Mocks::default(), 0);
+
+        let err = SolanaIndexer::new(
+            state.clone(),
+            "not-a-pubkey".to_string(),
+            "ws://127.0.0.1:8900".to_string(),
+            16,
+            IndexEventTypes::default(),
+        )
+        .err()
+        .unwrap();
+        assert!(err
+            .to_string()
+            .contains("invalid PROGRAM_ID 'not-a-pubkey'"));
+
+        let err = SolanaIndexer::new(
+            state,
+            Pubkey::new_unique().to_string(),
+            "http://127.0.0.1:8899".to_string(),
+            16,
+            IndexEventTypes::default(),
+        )
+        .err()
+        .unwrap();
+        assert!(err.to_string().contains("invalid SOLANA_WS_URL"));
+    }
+}
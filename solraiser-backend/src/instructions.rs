@@ -0,0 +1,173 @@
+//! Client-side encoding of SolRaiser program instructions, so the frontend only
+//! has to sign what the backend builds.
+
+use borsh::BorshSerialize;
+use solana_sdk::{
+    hash::hashv,
+    instruction::{AccountMeta, Instruction},
+    pubkey,
+    pubkey::Pubkey,
+};
+
+use crate::pda::{
+    derive_beneficiaries_pda, derive_campaign_pda, derive_creator_stats_pda, derive_registry_pda,
+};
+
+/// Mirrors `Campaign::MAX_METADATA_URL_LEN` in the program
+pub const MAX_METADATA_URL_LEN: usize = 256;
+
+/// Width of the zero-padded `Campaign::symbol`
+pub const SYMBOL_LEN: usize = 8;
+
+/// The runtime's compute budget program
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    pubkey!("ComputeBudget111111111111111111111111111111");
+
+/// Mirrors `Campaign::METADATA_URL_SCHEMES` in the program
+const METADATA_URL_SCHEMES: [&str; 3] = ["https://", "http://", "ipfs://"];
+
+/// First 8 bytes of `sha256("global:<name>")`, Anchor's instruction discriminator
+pub fn instruction_discriminator(name: &str) -> [u8; 8] {
+    let hash = hashv(&[b"global:", name.as_bytes()]);
+    let mut discriminator = [0u8; 8];
+    discriminator.copy_from_slice(&hash.to_bytes()[..8]);
+    discriminator
+}
+
+/// Same check the program runs in `create_campaign`, so a bad URL is rejected
+/// before the wallet is asked to sign
+pub fn is_valid_metadata_url(url: &str) -> bool {
+    if url.len() > MAX_METADATA_URL_LEN || url.chars().any(|c| c.is_whitespace() || c.is_control())
+    {
+        return false;
+    }
+
+    METADATA_URL_SCHEMES.iter().any(|scheme| {
+        url.strip_prefix(scheme)
+            .and_then(|rest| rest.split(['/', '?', '#']).next())
+            .is_some_and(|host| !host.is_empty())
+    })
+}
+
+/// Zero-pads an ASCII symbol such as "SOL" into the program's fixed-width field
+pub fn encode_symbol(symbol: &str) -> Option<[u8; SYMBOL_LEN]> {
+    if symbol.len() > SYMBOL_LEN || !symbol.bytes().all(|b| b.is_ascii_graphic()) {
+        return None;
+    }
+
+    let mut encoded = [0u8; SYMBOL_LEN];
+    encoded[..symbol.len()].copy_from_slice(symbol.as_bytes());
+    Some(encoded)
+}
+
+/// Compute budget `SetComputeUnitLimit`: caps the transaction at `units`
+pub fn set_compute_unit_limit(units: u32) -> Instruction {
+    let mut data = vec![2];
+    data.extend(units.to_le_bytes());
+    Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, Vec::new())
+}
+
+/// Compute budget `SetComputeUnitPrice`: a priority fee of `micro_lamports`
+/// per requested compute unit
+pub fn set_compute_unit_price(micro_lamports: u64) -> Instruction {
+    let mut data = vec![3];
+    data.extend(micro_lamports.to_le_bytes());
+    Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, Vec::new())
+}
+
+/// One recipient of a campaign's payout split, mirroring the program's `BeneficiaryShare`
+#[derive(Debug, Clone, BorshSerialize)]
+pub struct BeneficiaryShare {
+    pub pubkey: Pubkey,
+    pub share_bps: u16,
+}
+
+/// Arguments of the program's `create_campaign`, in IDL order
+#[derive(Debug, Clone, BorshSerialize)]
+pub struct CreateCampaignArgs {
+    pub goal_amount: u64,
+    pub deadline: i64,
+    pub metadata_url: String,
+    pub withdraw_cooldown_secs: u64,
+    pub decimals: u8,
+    pub symbol: [u8; SYMBOL_LEN],
+    /// Empty pays out to the creator
+    pub beneficiaries: Vec<BeneficiaryShare>,
+}
+
+/// Builds `create_campaign` for the campaign the registry will number
+/// `campaign_id` (its current `next_id`), whose PDA is derived from `creator`.
+/// The optional beneficiaries account is only passed for a split campaign; Anchor
+/// reads the program id in its place as "absent".
+pub fn create_campaign(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    campaign_id: u64,
+    args: &CreateCampaignArgs,
+) -> Instruction {
+    let mut data = instruction_discriminator("create_campaign").to_vec();
+    data.extend(borsh::to_vec(args).expect("borsh serialization into a Vec cannot fail"));
+
+    let campaign = derive_campaign_pda(program_id, creator, campaign_id).0;
+    let beneficiaries = if args.beneficiaries.is_empty() {
+        AccountMeta::new_readonly(*program_id, false)
+    } else {
+        AccountMeta::new(derive_beneficiaries_pda(program_id, &campaign).0, false)
+    };
+
+    Instruction {
+        program_id: *program_id,
+        accounts: vec![
+            AccountMeta::new(derive_registry_pda(program_id).0, false),
+            AccountMeta::new(campaign, false),
+            AccountMeta::new(derive_creator_stats_pda(program_id, creator).0, false),
+            beneficiaries,
+            AccountMeta::new(*creator, true),
+            AccountMeta::new_readonly(solana_system_interface::program::ID, false),
+        ],
+        data,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_campaign_discriminator_matches_idl() {
+        assert_eq!(
+            instruction_discriminator("create_campaign"),
+            [111, 131, 187, 98, 160, 193, 114, 244]
+        );
+    }
+
+    #[test]
+    fn metadata_url_validation_matches_program() {
+        assert!(is_valid_metadata_url("https://example.com/campaign.json"));
+        assert!(is_valid_metadata_url("ipfs://bafybeigdyrzt"));
+        assert!(!is_valid_metadata_url("my campaign"));
+        assert!(!is_valid_metadata_url("https:///no-host"));
+        assert!(!is_valid_metadata_url(&format!(
+            "https://example.com/{}",
+            "a".repeat(MAX_METADATA_URL_LEN)
+        )));
+    }
+
+    #[test]
+    fn compute_budget_instructions_match_the_runtime_encoding() {
+        assert_eq!(set_compute_unit_limit(200_000).data, [2, 64, 13, 3, 0]);
+        assert_eq!(
+            set_compute_unit_price(250).data,
+            [3, 250, 0, 0, 0, 0, 0, 0, 0]
+        );
+        assert!(set_compute_unit_price(250).accounts.is_empty());
+    }
+
+    #[test]
+    fn symbol_is_zero_padded_ascii() {
+        assert_eq!(encode_symbol("SOL"), Some(*b"SOL\0\0\0\0\0"));
+        assert_eq!(encode_symbol(""), Some([0; SYMBOL_LEN]));
+        assert_eq!(encode_symbol("TOOLONGSYM"), None);
+        assert_eq!(encode_symbol("S O"), None);
+    }
+}
@@ -1,6 +1,18 @@
+pub mod accounts;
+pub mod cli;
 pub mod config;
+pub mod db;
 pub mod error;
+pub mod expiry;
 pub mod handlers;
+pub mod idempotency;
 pub mod indexer;
+pub mod instructions;
+pub mod metadata;
 pub mod models;
+pub mod pda;
+pub mod rate_limit;
+pub mod reorg;
+pub mod routes;
+pub mod snapshot;
 pub mod state;
@@ -1,25 +1,29 @@
-use axum::{extract::Path, routing::get, Json, Router};
-use serde::{Deserialize, Serialize};
-use solraiser_backend::{config::Config, error::AppError, state::AppState};
+use std::{net::SocketAddr, sync::Arc, time::Duration};
+
+use clap::Parser;
+use solraiser_backend::{
+    cli::{self, Cli, Command},
+    config::Config,
+    db, expiry,
+    indexer::{self, SolanaIndexer},
+    rate_limit::{RateLimiter, RATE_LIMIT_WINDOW},
+    reorg, routes, snapshot,
+    state::AppState,
+};
 use sqlx::postgres::PgPoolOptions;
-use tower_http::cors::{Any, CorsLayer};
-
-#[derive(Debug, Serialize, Deserialize)]
-pub struct CampaignCreateRequest {
-    pub name: String,
-    pub description: String,
-    pub image_url: String,
-    pub target_amount: u64,
-    pub duration: u64,
-    pub creator: String,
-}
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    tracing_subscriber::fmt::init();
+
+    let command = Cli::parse().command();
     let config = Config::from_env()?;
+    let program_id = indexer::parse_program_id(&config.program_id)?;
 
     let db_pool = PgPoolOptions::new().connect(&config.database_url).await?;
 
+    db::run_migrations(&db_pool).await?;
+
     let start_slot = if let Some(slot) = config.start_slot {
         slot
     } else {
@@ -30,107 +34,88 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         max_slot.unwrap_or(0) as u64
     };
 
-    let app_state = AppState::new(db_pool, config.solana_rpc_url.clone(), start_slot);
-
-    // TODO: Start Indexer
-
-    // Router
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
-
-    let app = Router::new()
-        .route("/", get(root))
-        .route("/health", get(health_check))
-        .route("/transaction/:signature", get(get_transaction_by_signature))
-        .layer(cors)
-        .with_state(app_state);
-
-    let server_address = format!("{}:{}", config.server_host, config.server_port);
-
-    let listener = tokio::net::TcpListener::bind(server_address).await?;
-
-    println!(
-        "🚀 Server running on http://{}:{}",
-        config.server_host, config.server_port
-    );
-
-    axum::serve(listener, app).await?;
+    let app_state = AppState {
+        rpc_rate_limiter: Arc::new(RateLimiter::new(
+            config.rpc_proxy_rate_limit,
+            RATE_LIMIT_WINDOW,
+        )),
+        public_url: config.public_url.as_deref().map(Arc::from),
+        ..AppState::new(
+            db_pool,
+            config.solana_rpc_url.clone(),
+            program_id,
+            start_slot,
+        )
+    };
 
-    Ok(())
+    match command {
+        Command::Serve => serve(config, app_state).await,
+        Command::Reindex { signature } => {
+            indexer_for(&config, app_state)?
+                .reindex_signature(&signature)
+                .await?;
+            println!("Reindexed {}", signature);
+            Ok(())
+        }
+        Command::Verify { campaign_id } => {
+            if !cli::verify_campaign(&app_state, campaign_id).await? {
+                std::process::exit(1);
+            }
+            Ok(())
+        }
+    }
 }
 
-async fn root() -> &'static str {
-    "SolRaiser Backend API v1.0"
+fn indexer_for(config: &Config, app_state: AppState) -> anyhow::Result<SolanaIndexer> {
+    SolanaIndexer::new(
+        Arc::new(app_state),
+        config.program_id.clone(),
+        config.solana_ws_url.clone(),
+        config.channel_buffer_size,
+        config.index_event_types,
+    )
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-pub struct HealthRespone {
-    pub status: String,
-    pub timestamp: u64,
-}
+async fn serve(config: Config, app_state: AppState) -> Result<(), Box<dyn std::error::Error>> {
+    let solana_indexer = indexer_for(&config, app_state.clone())?;
+    solana_indexer.start().await?;
 
-async fn health_check() -> Result<Json<HealthRespone>, AppError> {
-    Ok(Json(HealthRespone {
-        status: "healthy".to_string(),
-        timestamp: std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .map_err(|e| AppError::InternalServerError(e.to_string()))?
-            .as_secs(),
-    }))
-}
+    reorg::spawn_reconciler(
+        Arc::new(app_state.clone()),
+        config.reorg_window_slots,
+        Duration::from_secs(config.reorg_check_interval_secs),
+    );
 
-#[derive(Serialize, Deserialize)]
-pub struct TransactionResponse {
-    pub signature: String,
-    pub data: serde_json::Value,
-}
+    snapshot::spawn_snapshotter(
+        Arc::new(app_state.clone()),
+        Duration::from_secs(config.snapshot_interval_secs),
+    );
 
-async fn get_transaction_by_signature(
-    Path(signature): Path<String>,
-) -> Result<Json<TransactionResponse>, AppError> {
-    if signature.is_empty() {
-        return Err(AppError::BadRequest(
-            "Transaction signature cannot be empty".to_string(),
-        ));
-    }
+    expiry::spawn_expiry_sweeper(
+        Arc::new(app_state.clone()),
+        Duration::from_secs(config.expiry_sweep_interval_secs),
+    );
 
-    let client = reqwest::Client::new();
-    let rpc_url = "https://api.devnet.solana.com";
-
-    let request_body = serde_json::json!({
-        "jsonrpc": "2.0",
-        "id": 1,
-        "method": "getTransaction",
-        "params": [
-            signature,
-            {
-                "encoding": "jsonParsed",
-                "maxSupportedTransactionVersion": 0
-            }
-        ]
-    });
+    // Router
+    let cors = routes::cors_layer(&config.allowed_origins)?;
 
-    let response = client.post(rpc_url).json(&request_body).send().await?;
+    let app = routes::router(app_state).layer(cors);
 
-    if !response.status().is_success() {
-        return Err(AppError::InternalServerError(format!(
-            "Failed to fetch transaction: {}",
-            response.status()
-        )));
-    }
+    let server_address = format!("{}:{}", config.server_host, config.server_port);
 
-    let rpc_response: serde_json::Value = response.json().await?;
+    let listener = tokio::net::TcpListener::bind(server_address).await?;
 
-    let data = rpc_response["result"].clone();
+    println!(
+        "🚀 Server running on http://{}:{}",
+        config.server_host, config.server_port
+    );
 
-    if data.is_null() {
-        return Err(AppError::BadRequest(format!(
-            "Transaction with signature '{}' not found or invalid response.",
-            signature
-        )));
-    }
+    // Client addresses feed the per-IP rate limits
+    axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
 
-    Ok(Json(TransactionResponse { signature, data }))
+    Ok(())
 }
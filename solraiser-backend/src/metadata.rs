@@ -0,0 +1,379 @@
+use std::{
+    net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr},
+    sync::Arc,
+    time::Duration,
+};
+
+use anyhow::{Context, Result};
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    redirect, Url,
+};
+use serde::Deserialize;
+use solana_sdk::hash::hashv;
+use sqlx::PgPool;
+use tokio::sync::Semaphore;
+
+const IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+const FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+/// Metadata documents larger than this are rejected unread
+pub const MAX_METADATA_BYTES: usize = 64 * 1024;
+/// Metadata fetches in flight at once; further ones wait for a slot
+pub const MAX_CONCURRENT_FETCHES: usize = 8;
+const MAX_REDIRECTS: usize = 5;
+
+/// The JSON document a campaign's `metadata_url` points to
+#[derive(Debug, Clone, Deserialize)]
+pub struct CampaignMetadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    #[serde(alias = "image_url")]
+    pub image: Option<String>,
+}
+
+/// Maps a metadata URL to something fetchable over HTTP (`ipfs://` goes through a gateway)
+pub fn resolve_url(metadata_url: &str) -> Option<String> {
+    if let Some(cid_path) = metadata_url.strip_prefix("ipfs://") {
+        return Some(format!("{}{}", IPFS_GATEWAY, cid_path));
+    }
+
+    if metadata_url.starts_with("https://") || metadata_url.starts_with("http://") {
+        return Some(metadata_url.to_string());
+    }
+
+    None
+}
+
+/// Whether `ip` is reachable on the public internet. Metadata URLs come from
+/// campaign creators, so fetching anything else would let them probe the
+/// indexer's own network.
+pub fn is_public_ip(ip: IpAddr) -> bool {
+    match ip {
+        IpAddr::V4(ip) => is_public_ipv4(ip),
+        IpAddr::V6(ip) => match embedded_ipv4(ip) {
+            Some(ip) => is_public_ipv4(ip),
+            None => {
+                let [first, second, third, fourth, ..] = ip.segments();
+                !(ip.is_loopback()
+                    || ip.is_unspecified()
+                    || ip.is_multicast()
+                    || (first == 0x0100 && second == 0 && third == 0 && fourth == 0) // discard, 100::/64
+                    || (first == 0x2001 && second == 0x0db8) // documentation, 2001:db8::/32
+                    || (first & 0xfe00) == 0xfc00 // unique local, fc00::/7
+                    || (first & 0xffc0) == 0xfec0 // site-local, fec0::/10
+                    || (first & 0xffc0) == 0xfe80) // link-local, fe80::/10
+            }
+        },
+    }
+}
+
+/// The IPv4 address an IPv6 address routes to: IPv4-mapped `::ffff:a.b.c.d`,
+/// IPv4-compatible `::a.b.c.d` and NAT64 `64:ff9b::a.b.c.d`
+fn embedded_ipv4(ip: Ipv6Addr) -> Option<Ipv4Addr> {
+    if let Some(ip) = ip.to_ipv4_mapped() {
+        return Some(ip);
+    }
+    let [prefix @ .., high, low] = ip.segments();
+    let ipv4 = Ipv4Addr::from((u32::from(high) << 16) | u32::from(low));
+    match prefix {
+        // `::` and `::1` are IPv6's own unspecified and loopback addresses
+        [0, 0, 0, 0, 0, 0] if high != 0 => Some(ipv4),
+        [0x64, 0xff9b, 0, 0, 0, 0] => Some(ipv4),
+        _ => None,
+    }
+}
+
+fn is_public_ipv4(ip: Ipv4Addr) -> bool {
+    let [a, b, ..] = ip.octets();
+    !(ip.is_private()
+        || ip.is_loopback()
+        || ip.is_link_local()
+        || ip.is_unspecified()
+        || ip.is_broadcast()
+        || ip.is_multicast()
+        || ip.is_documentation()
+        || (a == 100 && (64..128).contains(&b)) // carrier-grade NAT, 100.64.0.0/10
+        || a == 0)
+}
+
+/// Resolves hostnames like the system resolver, but only to public addresses,
+/// so a host can't be pointed at an internal address between check and connect
+struct PublicResolver;
+
+impl Resolve for PublicResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        Box::pin(async move {
+            let addrs: Vec<SocketAddr> = tokio::net::lookup_host((name.as_str(), 0))
+                .await?
+                .filter(|addr| is_public_ip(addr.ip()))
+                .collect();
+            if addrs.is_empty() {
+                return Err(format!("'{}' has no public address", name.as_str()).into());
+            }
+            let addrs: Addrs = Box::new(addrs.into_iter());
+            Ok(addrs)
+        })
+    }
+}
+
+/// Rejects URLs whose host is a literal non-public IP address; hostnames are
+/// checked by [`PublicResolver`] when connecting
+fn check_url_host(url: &Url) -> Result<()> {
+    let host = url
+        .host_str()
+        .with_context(|| format!("Metadata URL '{}' has no host", url))?;
+    // IPv6 hosts keep their brackets
+    let Ok(ip) = host.trim_start_matches('[').trim_end_matches(']').parse() else {
+        return Ok(());
+    };
+    anyhow::ensure!(
+        is_public_ip(ip),
+        "Metadata URL '{}' is not a public address",
+        url
+    );
+    Ok(())
+}
+
+/// HTTP client and concurrency limit shared by every metadata fetch
+pub struct MetadataFetcher {
+    client: reqwest::Client,
+    permits: Semaphore,
+}
+
+impl Default for MetadataFetcher {
+    fn default() -> Self {
+        MetadataFetcher::new(MAX_CONCURRENT_FETCHES)
+    }
+}
+
+impl MetadataFetcher {
+    /// Allows `max_concurrent` fetches at once
+    pub fn new(max_concurrent: usize) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(FETCH_TIMEOUT)
+            .dns_resolver(Arc::new(PublicResolver))
+            .redirect(redirect::Policy::custom(|attempt| {
+                if attempt.previous().len() >= MAX_REDIRECTS {
+                    attempt.error("too many redirects")
+                } else if let Err(e) = check_url_host(attempt.url()) {
+                    attempt.error(e.to_string())
+                } else {
+                    attempt.follow()
+                }
+            }))
+            .build()
+            .expect("metadata HTTP client configuration is valid");
+        MetadataFetcher {
+            client,
+            permits: Semaphore::new(max_concurrent),
+        }
+    }
+
+    /// Fetches and parses the metadata document at `url`, reading at most
+    /// [`MAX_METADATA_BYTES`] of it
+    async fn fetch(&self, url: &str) -> Result<CampaignMetadata> {
+        let url = Url::parse(url).context("Invalid metadata URL")?;
+        check_url_host(&url)?;
+
+        let _permit = self
+            .permits
+            .acquire()
+            .await
+            .context("Metadata fetcher closed")?;
+        let response = self
+            .client
+            .get(url)
+            .send()
+            .await
+            .context("Failed to fetch campaign metadata")?
+            .error_for_status()
+            .context("Metadata host returned an error")?;
+
+        let body = read_capped(response, MAX_METADATA_BYTES).await?;
+        serde_json::from_slice(&body).context("Campaign metadata is not valid JSON")
+    }
+}
+
+/// Reads `response`'s body chunk by chunk, failing as soon as it exceeds `limit` bytes
+async fn read_capped(mut response: reqwest::Response, limit: usize) -> Result<Vec<u8>> {
+    if response
+        .content_length()
+        .is_some_and(|len| len > limit as u64)
+    {
+        anyhow::bail!("Campaign metadata is larger than {} bytes", limit);
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = response
+        .chunk()
+        .await
+        .context("Failed to read campaign metadata")?
+    {
+        if body.len() + chunk.len() > limit {
+            anyhow::bail!("Campaign metadata is larger than {} bytes", limit);
+        }
+        body.extend_from_slice(&chunk);
+    }
+    Ok(body)
+}
+
+/// Fetches a campaign's off-chain metadata and upserts it into `campaign_metadata`
+pub async fn cache_campaign_metadata(
+    fetcher: &MetadataFetcher,
+    db: &PgPool,
+    campaign_id: i64,
+    metadata_url: &str,
+) -> Result<()> {
+    let url = resolve_url(metadata_url)
+        .with_context(|| format!("Unsupported metadata URL '{}'", metadata_url))?;
+
+    let metadata = fetcher.fetch(&url).await?;
+
+    sqlx::query!(
+        r#"
+        INSERT INTO campaign_metadata (campaign_id, metadata_url, name, description, image_url)
+        VALUES ($1, $2, $3, $4, $5)
+        ON CONFLICT (campaign_id) DO UPDATE
+        SET metadata_url = EXCLUDED.metadata_url,
+            name = EXCLUDED.name,
+            description = EXCLUDED.description,
+            image_url = EXCLUDED.image_url,
+            fetched_at = CURRENT_TIMESTAMP
+        "#,
+        campaign_id,
+        metadata_url,
+        metadata.name,
+        metadata.description,
+        metadata.image,
+    )
+    .execute(db)
+    .await
+    .context("Failed to cache campaign metadata")?;
+
+    Ok(())
+}
+
+/// Stores a metadata document for `GET /v1/metadata/:id` to serve and returns
+/// its id, a hash of the document, so resubmitting it stores nothing new
+pub async fn host_metadata(
+    db: &PgPool,
+    name: &str,
+    description: &str,
+    image_url: &str,
+) -> Result<String> {
+    let id = hashv(&[
+        name.as_bytes(),
+        &[0],
+        description.as_bytes(),
+        &[0],
+        image_url.as_bytes(),
+    ])
+    .to_string();
+
+    sqlx::query!(
+        r#"
+        INSERT INTO hosted_metadata (id, name, description, image_url)
+        VALUES ($1, $2, $3, $4)
+        ON CONFLICT (id) DO NOTHING
+        "#,
+        id,
+        name,
+        description,
+        image_url,
+    )
+    .execute(db)
+    .await
+    .context("Failed to store campaign metadata")?;
+
+    Ok(id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_url_routes_ipfs_through_gateway() {
+        assert_eq!(
+            resolve_url("ipfs://bafy123/meta.json").as_deref(),
+            Some("https://ipfs.io/ipfs/bafy123/meta.json")
+        );
+        assert_eq!(
+            resolve_url("https://example.com/meta.json").as_deref(),
+            Some("https://example.com/meta.json")
+        );
+        assert_eq!(resolve_url("not a url"), None);
+    }
+
+    #[test]
+    fn only_public_addresses_are_fetchable() {
+        for ip in [
+            "93.184.216.34",
+            "2606:2800:220:1::1",
+            "64:ff9b::93.184.216.34",
+        ] {
+            assert!(is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+        for ip in [
+            "127.0.0.1",
+            "10.1.2.3",
+            "172.16.0.1",
+            "192.168.1.1",
+            "169.254.169.254",
+            "100.64.0.1",
+            "0.0.0.0",
+            "::1",
+            "fd00::1",
+            "fe80::1",
+            "::ffff:127.0.0.1",
+            "::127.0.0.1",
+            "::10.0.0.1",
+            "64:ff9b::169.254.169.254",
+            "64:ff9b::10.1.2.3",
+            "2001:db8::1",
+            "fec0::1",
+            "100::1",
+        ] {
+            assert!(!is_public_ip(ip.parse().unwrap()), "{ip}");
+        }
+    }
+
+    #[tokio::test]
+    async fn internal_urls_are_not_fetched() {
+        let fetcher = MetadataFetcher::default();
+        for url in [
+            "http://127.0.0.1:1/meta.json",
+            "http://[::1]/meta.json",
+            "http://169.254.169.254/latest/meta-data",
+            "http://localhost:1/meta.json",
+        ] {
+            let err = fetcher.fetch(url).await.unwrap_err();
+            assert!(
+                format!("{:?}", err).contains("public address"),
+                "{url}: {err:?}"
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn oversized_metadata_is_rejected() {
+        let body = "x".repeat(MAX_METADATA_BYTES + 1);
+        let app = axum::Router::new().route("/", axum::routing::get(move || async move { body }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let response = reqwest::get(&url).await.unwrap();
+        let err = read_capped(response, MAX_METADATA_BYTES).await.unwrap_err();
+        assert!(err.to_string().contains("larger than"));
+
+        let response = reqwest::get(&url).await.unwrap();
+        assert_eq!(
+            read_capped(response, MAX_METADATA_BYTES + 1)
+                .await
+                .unwrap()
+                .len(),
+            MAX_METADATA_BYTES + 1
+        );
+    }
+}
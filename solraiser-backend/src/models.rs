@@ -21,6 +21,8 @@ pub struct Transaction {
     pub block_time: Option<i64>,
     pub success: bool,
     pub fee: Option<i64>,
+    /// `{ "err": ..., "logs": [...] }` for failed transactions
+    pub error_detail: Option<serde_json::Value>,
     pub indexed_at: DateTime<Utc>,
 }
 
@@ -35,3 +37,318 @@ pub struct AccountUpdates {
     pub data: Option<String>,
     pub indexed_at: DateTime<Utc>,
 }
+
+/// Represents a decoded SolRaiser program event stored in the database
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CampaignEventRecord {
+    pub id: i32,
+    pub signature: String,
+    pub slot: i64,
+    /// Position among the SolRaiser events emitted by the same transaction
+    pub event_index: i32,
+    pub event_type: String,
+    pub campaign_id: i64,
+    pub user_pubkey: String,
+    pub amount: Option<i64>,
+    pub goal_amount: Option<i64>,
+    pub deadline: Option<i64>,
+    pub metadata_url: Option<String>,
+    pub goal_reached_at: Option<i64>,
+    /// Campaign total including this donation; only set on `donated` events
+    pub amount_raised: Option<i64>,
+    /// Display precision of amounts; only set on `created` events
+    pub decimals: Option<i16>,
+    pub symbol: Option<String>,
+    pub indexed_at: DateTime<Utc>,
+    /// Log lines of the instruction that emitted the event; only returned when
+    /// asked for with `include_logs=true`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_logs: Option<Vec<String>>,
+}
+
+/// Position after the last event of a page. `(slot, id)` orders events totally,
+/// so resuming from it neither skips nor repeats events sharing a slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventCursor {
+    pub since_slot: i64,
+    pub since_id: i32,
+}
+
+/// A page of events in ascending `(slot, id)` order. `next_cursor` is passed
+/// back as `since_slot` and `since_id` to fetch the following page; it is the
+/// request's own cursor when the page is empty.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventPage {
+    pub events: Vec<CampaignEventRecord>,
+    pub next_cursor: EventCursor,
+}
+
+/// A created campaign with its totals reconstructed from indexed events
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CampaignSummary {
+    pub campaign_id: i64,
+    pub creator_pubkey: String,
+    pub goal_amount: Option<i64>,
+    pub deadline: Option<i64>,
+    pub metadata_url: Option<String>,
+    pub amount_raised: i64,
+    pub donation_count: i64,
+    pub withdrawn_amount: i64,
+    pub created_slot: i64,
+    /// How to display the amounts above, e.g. 9 and "SOL" for lamports
+    pub decimals: Option<i16>,
+    pub symbol: Option<String>,
+    /// Whether `amount_raised` matches the on-chain account; `None` until the
+    /// campaign has been snapshotted
+    pub reconciled: Option<bool>,
+    /// On-chain `amount_raised` minus the event-derived one; positive when
+    /// donation events were missed
+    pub discrepancy_lamports: Option<i64>,
+    /// Slot of the campaign's latest event; changes whenever the summary may have
+    pub last_event_slot: i64,
+    /// Block time of the campaign's latest event, if the block's time is known
+    pub updated_at: Option<DateTime<Utc>>,
+    /// Share of the goal raised in basis points, capped at 10000; see
+    /// [`crate::accounts::progress_bps`]
+    pub progress_bps: i32,
+    pub is_goal_reached: bool,
+}
+
+/// A campaign matched by `/search`, with its cached metadata and relevance
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CampaignSearchResult {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub summary: CampaignSummary,
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+    pub rank: f32,
+}
+
+/// An active campaign listed by `/campaigns/ending-soon`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct EndingSoonCampaign {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub summary: CampaignSummary,
+    pub name: Option<String>,
+    pub image_url: Option<String>,
+    pub seconds_remaining: i64,
+}
+
+/// Where a campaign is in its lifecycle, as last recorded in `campaign_statuses`
+/// by [`crate::expiry::sweep_campaign_statuses`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, sqlx::Type)]
+#[serde(rename_all = "lowercase")]
+#[sqlx(type_name = "varchar", rename_all = "lowercase")]
+pub enum CampaignStatus {
+    /// Deadline ahead, accepting donations; also campaigns not yet swept
+    Active,
+    /// Deadline passed with the goal met, nothing withdrawn yet
+    Succeeded,
+    /// Deadline passed with the goal unmet, nothing withdrawn yet
+    Failed,
+    /// Fully withdrawn; after a partial withdrawal the campaign stays `Succeeded`
+    Withdrawn,
+}
+
+/// A campaign summary with its lifecycle status, listed by `/campaigns`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CampaignListing {
+    #[serde(flatten)]
+    #[sqlx(flatten)]
+    pub summary: CampaignSummary,
+    pub status: CampaignStatus,
+}
+
+/// Off-chain metadata cached from a campaign's `metadata_url`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CampaignMetadata {
+    pub name: Option<String>,
+    pub description: Option<String>,
+    pub image_url: Option<String>,
+}
+
+/// Everything a campaign page needs, served by `/campaigns/:id`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CampaignDetail {
+    #[serde(flatten)]
+    pub summary: CampaignSummary,
+    pub status: CampaignStatus,
+    /// `None` until the campaign's metadata is fetched
+    pub metadata: Option<CampaignMetadata>,
+    /// Newest first
+    pub recent_events: Vec<CampaignEventRecord>,
+}
+
+/// A donor's total to one campaign, listed by `/campaigns/:id/top-donors`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct TopDonor {
+    pub donor: String,
+    pub total_amount: i64,
+    pub donation_count: i64,
+}
+
+/// Whether a donor can reclaim their donations to a campaign, served by
+/// `/campaigns/:id/refund-status`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RefundStatus {
+    pub campaign_id: i64,
+    pub donor: String,
+    /// Deadline passed with the goal unmet
+    pub campaign_failed: bool,
+    /// Total the donor donated to the campaign
+    pub contributed: i64,
+    /// Always `false` for now: the program has no refund instruction
+    pub eligible: bool,
+    /// Why the donor can't get a refund
+    pub reason: String,
+}
+
+impl RefundStatus {
+    pub const REFUNDS_UNSUPPORTED: &'static str = "Refunds are not supported by the program yet";
+
+    pub fn evaluate(summary: &CampaignSummary, donor: String, contributed: i64, now: i64) -> Self {
+        let active = summary.deadline.is_some_and(|deadline| deadline > now);
+        let goal_reached = summary
+            .goal_amount
+            .is_some_and(|goal| summary.amount_raised >= goal);
+        let campaign_failed = !active && !goal_reached;
+
+        let reason = if active {
+            "Campaign is still active"
+        } else if goal_reached {
+            "Campaign reached its goal"
+        } else if contributed == 0 {
+            "Donor has not donated to this campaign"
+        } else {
+            Self::REFUNDS_UNSUPPORTED
+        };
+
+        RefundStatus {
+            campaign_id: summary.campaign_id,
+            donor,
+            campaign_failed,
+            contributed,
+            eligible: false,
+            reason: reason.to_string(),
+        }
+    }
+}
+
+/// Total raised by a campaign as of the end of one time bucket
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ProgressPoint {
+    /// Start of the bucket
+    pub t: DateTime<Utc>,
+    pub cumulative_amount: i64,
+}
+
+/// One donation in a donor's history
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DonorDonation {
+    pub signature: String,
+    pub slot: i64,
+    pub block_time: Option<i64>,
+    pub campaign_id: i64,
+    /// From the metadata cache; `None` until the campaign's metadata is fetched
+    pub campaign_name: Option<String>,
+    pub amount: i64,
+}
+
+/// A page of a donor's donations plus totals across every page
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DonorHistory {
+    pub donor: String,
+    pub total_amount: i64,
+    pub donation_count: i64,
+    pub donations: Vec<DonorDonation>,
+}
+
+/// Body of `POST /campaigns/build-create-tx`
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CampaignCreateRequest {
+    pub name: String,
+    pub description: String,
+    /// http(s) or ipfs URL of the campaign's image
+    pub image_url: String,
+    /// Goal in lamports; must exceed the campaign account's rent by
+    /// [`crate::accounts::MIN_GOAL_ABOVE_RENT`]
+    pub target_amount: u64,
+    /// Seconds from now until the deadline
+    pub duration: u64,
+    pub creator: String,
+    /// Minimum seconds between withdrawals; 0 (the default) disables the cooldown
+    #[serde(default)]
+    pub withdraw_cooldown_secs: u64,
+    /// Display precision of `target_amount`; defaults to 9 (SOL)
+    pub decimals: Option<u8>,
+    /// Up to 8 ASCII characters; defaults to "SOL"
+    pub symbol: Option<String>,
+}
+
+/// What creating a campaign costs the creator, served by `/campaigns/fee-estimate`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FeeEstimate {
+    /// Rent-exempt minimum locked in the campaign account
+    pub rent_lamports: u64,
+    /// Rent-exempt minimum of the creator's stats account, paid with their
+    /// first campaign; 0 once it exists
+    pub creator_stats_rent_lamports: u64,
+    /// Base fee for the creator's signature
+    pub transaction_fee_lamports: u64,
+    /// Median recent priority fee paid for the program, in micro-lamports per compute unit
+    pub priority_fee_micro_lamports: u64,
+    pub compute_units: u64,
+    pub priority_fee_lamports: u64,
+    pub total_lamports: u64,
+    pub total_sol: f64,
+}
+
+impl FeeEstimate {
+    pub const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+    /// Compute unit limit `build-create-tx` requests, which is what its
+    /// priority fee is charged on
+    pub const CREATE_CAMPAIGN_COMPUTE_UNITS: u32 = 200_000;
+
+    pub fn new(
+        rent_lamports: u64,
+        creator_stats_rent_lamports: u64,
+        priority_fee_micro_lamports: u64,
+    ) -> Self {
+        let compute_units = Self::CREATE_CAMPAIGN_COMPUTE_UNITS as u64;
+        let priority_fee_lamports = (priority_fee_micro_lamports as u128 * compute_units as u128)
+            .div_ceil(1_000_000) as u64;
+        let total_lamports = rent_lamports
+            + creator_stats_rent_lamports
+            + Self::LAMPORTS_PER_SIGNATURE
+            + priority_fee_lamports;
+
+        FeeEstimate {
+            rent_lamports,
+            creator_stats_rent_lamports,
+            transaction_fee_lamports: Self::LAMPORTS_PER_SIGNATURE,
+            priority_fee_micro_lamports,
+            compute_units,
+            priority_fee_lamports,
+            total_lamports,
+            total_sol: total_lamports as f64 / 1_000_000_000.0,
+        }
+    }
+}
+
+/// An unsigned `create_campaign` transaction for the creator's wallet to sign
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CampaignCreateTransaction {
+    /// Base64 of the bincode-serialized transaction
+    pub transaction: String,
+    /// Id the registry will assign; if another campaign is created first the
+    /// transaction fails and must be rebuilt
+    pub campaign_id: u64,
+    pub campaign_pda: String,
+    pub deadline: i64,
+    pub recent_blockhash: String,
+    /// Where the API serves the campaign's name/description/image, as stored on-chain
+    pub metadata_url: String,
+}
@@ -0,0 +1,87 @@
+//! Program-derived addresses, using exactly the seeds the SolRaiser program
+//! declares in its account constraints.
+//!
+//! Every helper takes the program id first, since the backend can be pointed
+//! at any deployment with `PROGRAM_ID`. Donations have no PDA to derive: the
+//! program adds them to the campaign account's balance and only records them
+//! in `CampaignDonated` events.
+
+use solana_sdk::pubkey::Pubkey;
+
+/// Seed prefix of campaign accounts
+pub const CAMPAIGN_SEED: &[u8] = b"campaign";
+/// Seed of the global registry that assigns campaign ids
+pub const REGISTRY_SEED: &[u8] = b"registry";
+/// Seed prefix of per-creator campaign counters
+pub const CREATOR_STATS_SEED: &[u8] = b"creator_stats";
+/// Seed prefix of a campaign's payout split
+pub const BENEFICIARIES_SEED: &[u8] = b"beneficiaries";
+
+/// Registry PDA and bump, seeded with `["registry"]`
+pub fn derive_registry_pda(program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_SEED], program_id)
+}
+
+/// Creator stats PDA and bump, seeded with `["creator_stats", creator]`
+pub fn derive_creator_stats_pda(program_id: &Pubkey, creator: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CREATOR_STATS_SEED, creator.as_ref()], program_id)
+}
+
+/// Campaign PDA and bump, seeded with `["campaign", creator, campaign_id (LE)]`
+pub fn derive_campaign_pda(
+    program_id: &Pubkey,
+    creator: &Pubkey,
+    campaign_id: u64,
+) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CAMPAIGN_SEED, creator.as_ref(), &campaign_id.to_le_bytes()],
+        program_id,
+    )
+}
+
+/// Beneficiaries PDA and bump, seeded with `["beneficiaries", campaign]`
+pub fn derive_beneficiaries_pda(program_id: &Pubkey, campaign: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[BENEFICIARIES_SEED, campaign.as_ref()], program_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PROGRAM_ID: &str = "62NbBCCxPfR83xtgw3AaxKGHyyDdxobrcCGzA7s7LFie";
+    const CREATOR: &str = "9WzDXwBbmkg8ZTbNMqUxvQRAyrZzDsGYdLVL9zYtAWWM";
+
+    /// A different address here means the seeds drifted from the program's
+    #[test]
+    fn campaign_pda_matches_fixture() {
+        let program_id: Pubkey = PROGRAM_ID.parse().unwrap();
+        let creator: Pubkey = CREATOR.parse().unwrap();
+
+        let (pda, bump) = derive_campaign_pda(&program_id, &creator, 1_700_000_000_000);
+
+        assert_eq!(
+            pda.to_string(),
+            "23QqvGW5bLXdU4uZGASYQSPy6rPFqR3F6WG2XrXJBXBV"
+        );
+        assert_eq!(bump, 255);
+    }
+
+    #[test]
+    fn campaign_id_is_little_endian() {
+        let program_id: Pubkey = PROGRAM_ID.parse().unwrap();
+        let creator: Pubkey = CREATOR.parse().unwrap();
+        let (pda, bump) = derive_campaign_pda(&program_id, &creator, 1);
+
+        let expected = Pubkey::create_program_address(
+            &[
+                b"campaign",
+                creator.as_ref(),
+                &[1, 0, 0, 0, 0, 0, 0, 0],
+                &[bump],
+            ],
+            &program_id,
+        )
+        .unwrap();
+        assert_eq!(pda, expected);
+    }
+}
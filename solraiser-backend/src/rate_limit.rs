@@ -0,0 +1,120 @@
+//! Per-client-IP request limits for routes that cost more than a database read,
+//! such as the RPC proxy.
+
+use std::{
+    collections::HashMap,
+    net::{IpAddr, Ipv4Addr, SocketAddr},
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use axum::{
+    extract::{ConnectInfo, Request, State},
+    http::{header, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::{error::AppError, state::AppState};
+
+pub const DEFAULT_RATE_LIMIT: u32 = 60;
+pub const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+struct Window {
+    started_at: Instant,
+    requests: u32,
+}
+
+/// Fixed-window counter of requests per client IP, kept in memory
+pub struct RateLimiter {
+    limit: u32,
+    window: Duration,
+    clients: Mutex<HashMap<IpAddr, Window>>,
+}
+
+impl Default for RateLimiter {
+    fn default() -> Self {
+        RateLimiter::new(DEFAULT_RATE_LIMIT, RATE_LIMIT_WINDOW)
+    }
+}
+
+impl RateLimiter {
+    /// Allows `limit` requests per client in every `window`
+    pub fn new(limit: u32, window: Duration) -> Self {
+        RateLimiter {
+            limit,
+            window,
+            clients: Mutex::default(),
+        }
+    }
+
+    /// Counts a request from `ip`. Over the limit, returns how long until the
+    /// client's window resets.
+    fn check(&self, ip: IpAddr, now: Instant) -> Result<(), Duration> {
+        let mut clients = self.clients.lock().unwrap();
+        clients.retain(|_, window| now.duration_since(window.started_at) < self.window);
+
+        let window = clients.entry(ip).or_insert(Window {
+            started_at: now,
+            requests: 0,
+        });
+        if window.requests >= self.limit {
+            return Err(self.window - now.duration_since(window.started_at));
+        }
+        window.requests += 1;
+        Ok(())
+    }
+}
+
+/// Middleware answering `429 Too Many Requests`, with `Retry-After`, once a
+/// client IP goes over [`AppState::rpc_rate_limiter`]. Clients are told apart by
+/// their socket address, so behind a reverse proxy they all share one limit;
+/// requests without connection info (e.g. in tests) share one as well.
+pub async fn rate_limit(State(state): State<AppState>, request: Request, next: Next) -> Response {
+    let ip = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+
+    match state.rpc_rate_limiter.check(ip, Instant::now()) {
+        Ok(()) => next.run(request).await,
+        Err(retry_after) => {
+            let retry_after = retry_after.as_secs().max(1);
+            let mut response = AppError::TooManyRequests(format!(
+                "Rate limit exceeded, retry in {} seconds",
+                retry_after
+            ))
+            .into_response();
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(retry_after));
+            response
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_each_client_per_window() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let client = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 1));
+        let other = IpAddr::V4(Ipv4Addr::new(10, 0, 0, 2));
+        let start = Instant::now();
+
+        assert!(limiter.check(client, start).is_ok());
+        assert!(limiter.check(client, start).is_ok());
+        assert_eq!(
+            limiter.check(client, start + Duration::from_secs(15)),
+            Err(Duration::from_secs(45))
+        );
+        assert!(limiter.check(other, start).is_ok());
+
+        assert!(limiter
+            .check(client, start + Duration::from_secs(60))
+            .is_ok());
+    }
+}
@@ -0,0 +1,142 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{Context, Result};
+use solana_sdk::signature::Signature;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::state::AppState;
+
+/// `getSignatureStatuses` accepts at most 256 signatures per call
+const STATUS_BATCH_SIZE: usize = 256;
+
+/// Consecutive checks without a status after which an event is flagged orphaned.
+/// A node that is behind or pruned can briefly miss a transaction that is still
+/// on the chain, so one miss alone proves nothing.
+pub const ORPHAN_AFTER_MISSES: i16 = 3;
+
+/// Flags events from recently indexed slots whose transaction the cluster no
+/// longer knows about (dropped by a fork) in [`ORPHAN_AFTER_MISSES`] checks in a
+/// row, and restores flagged events whose transaction turns up again. Only
+/// slots within `window_slots` of the last indexed slot are checked. Returns
+/// the number of events newly flagged.
+pub async fn reconcile_recent_events(state: &AppState, window_slots: u64) -> Result<u64> {
+    let last_slot = *state
+        .last_indexed_slot
+        .read()
+        .map_err(|e| anyhow::anyhow!("Failed to acquire read lock: {}", e))?;
+    let from_slot = last_slot.saturating_sub(window_slots) as i64;
+
+    let signatures = sqlx::query_scalar!(
+        r#"
+        SELECT DISTINCT signature FROM campaign_events
+        WHERE slot >= $1
+        ORDER BY signature
+        "#,
+        from_slot,
+    )
+    .fetch_all(&state.db)
+    .await
+    .context("Failed to load recent signatures")?;
+
+    let mut found = Vec::new();
+    let mut missing = Vec::new();
+    for chunk in signatures.chunks(STATUS_BATCH_SIZE) {
+        let parsed = chunk
+            .iter()
+            .map(|s| s.parse::<Signature>())
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .context("Failed to parse stored signature")?;
+
+        let rpc_client = state.rpc_client.clone();
+        let statuses = tokio::task::spawn_blocking(move || {
+            rpc_client.get_signature_statuses_with_history(&parsed)
+        })
+        .await
+        .context("Failed to spawn status lookup")?
+        .context("Failed to fetch signature statuses")?
+        .value;
+
+        for (signature, status) in chunk.iter().zip(statuses) {
+            match status {
+                Some(_) => found.push(signature.clone()),
+                None => missing.push(signature.clone()),
+            }
+        }
+    }
+
+    let restored = sqlx::query!(
+        r#"
+        UPDATE campaign_events SET orphaned = FALSE, status_misses = 0
+        WHERE signature = ANY($1) AND (orphaned OR status_misses > 0)
+        "#,
+        &found,
+    )
+    .execute(&state.db)
+    .await
+    .context("Failed to reset status misses")?
+    .rows_affected();
+    if restored > 0 {
+        info!("Transactions of {} events were found again", restored);
+    }
+
+    if missing.is_empty() {
+        return Ok(0);
+    }
+
+    sqlx::query!(
+        r#"
+        UPDATE campaign_events SET status_misses = LEAST(status_misses + 1, $2::SMALLINT)
+        WHERE signature = ANY($1)
+        "#,
+        &missing,
+        ORPHAN_AFTER_MISSES,
+    )
+    .execute(&state.db)
+    .await
+    .context("Failed to count status misses")?;
+
+    let flagged = sqlx::query!(
+        r#"
+        UPDATE campaign_events SET orphaned = TRUE
+        WHERE signature = ANY($1) AND NOT orphaned AND status_misses >= $2
+        "#,
+        &missing,
+        ORPHAN_AFTER_MISSES,
+    )
+    .execute(&state.db)
+    .await
+    .context("Failed to flag orphaned events")?
+    .rows_affected();
+
+    if flagged > 0 {
+        warn!(
+            "Flagged {} events as orphaned; {} transactions are currently unknown to the cluster",
+            flagged,
+            missing.len()
+        );
+    }
+    Ok(flagged)
+}
+
+/// Runs [`reconcile_recent_events`] every `interval`
+pub fn spawn_reconciler(
+    state: Arc<AppState>,
+    window_slots: u64,
+    interval: Duration,
+) -> JoinHandle<()> {
+    info!(
+        "Reorg reconciler checking the last {} slots every {:?}",
+        window_slots, interval
+    );
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = reconcile_recent_events(&state, window_slots).await {
+                error!("Reorg reconciliation error: {:?}", e);
+            }
+        }
+    })
+}
@@ -0,0 +1,105 @@
+use anyhow::Context;
+use axum::{
+    extract::DefaultBodyLimit,
+    http::{header, HeaderValue, Method},
+    middleware,
+    routing::{get, post},
+    Router,
+};
+use tower_http::cors::{Any, CorsLayer};
+
+use crate::{config::AllowedOrigins, handlers, idempotency, rate_limit, state::AppState};
+
+/// CORS for [`router`]. An explicit origin list also narrows methods and
+/// headers to the ones the API actually uses.
+pub fn cors_layer(allowed_origins: &AllowedOrigins) -> anyhow::Result<CorsLayer> {
+    let origins = match allowed_origins {
+        AllowedOrigins::Any => {
+            return Ok(CorsLayer::new()
+                .allow_origin(Any)
+                .allow_methods(Any)
+                .allow_headers(Any))
+        }
+        AllowedOrigins::List(origins) => origins
+            .iter()
+            .map(|origin| {
+                HeaderValue::from_str(origin)
+                    .with_context(|| format!("Invalid CORS origin '{}'", origin))
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?,
+    };
+
+    Ok(CorsLayer::new()
+        .allow_origin(origins)
+        .allow_methods([Method::GET, Method::POST])
+        .allow_headers([header::CONTENT_TYPE, idempotency::IDEMPOTENCY_KEY]))
+}
+
+/// Builds the API router; cross-cutting layers (CORS) are applied by the caller.
+/// Data routes live under `/v1`; `/`, `/health` and `/version` stay unprefixed
+/// so clients can discover the API before picking a version.
+pub fn router(state: AppState) -> Router {
+    Router::new()
+        .route("/", get(handlers::root))
+        .route("/health", get(handlers::health_check))
+        .route("/version", get(handlers::get_version))
+        .nest("/v1", v1_routes(&state))
+        .with_state(state)
+}
+
+fn v1_routes(state: &AppState) -> Router<AppState> {
+    Router::new()
+        .route(
+            "/transaction/:signature",
+            get(handlers::get_rpc_transaction),
+        )
+        .route(
+            "/transaction/:signature/events",
+            get(handlers::get_transaction_events),
+        )
+        .route(
+            "/transactions/:signature",
+            get(handlers::get_transaction_by_signature),
+        )
+        .route("/blocks", get(handlers::get_blocks))
+        .route("/blocks/recent", get(handlers::get_recent_blocks))
+        .route("/blocks/:slot", get(handlers::get_block))
+        .route("/events", get(handlers::get_events))
+        .route("/search", get(handlers::search_campaigns))
+        .route("/campaigns", get(handlers::get_campaigns))
+        .route("/campaigns/stream", get(handlers::stream_campaigns))
+        .route(
+            "/campaigns/build-create-tx",
+            post(handlers::build_create_campaign_tx).route_layer(middleware::from_fn_with_state(
+                state.clone(),
+                idempotency::idempotency,
+            )),
+        )
+        .route("/campaigns/fee-estimate", get(handlers::get_fee_estimate))
+        .route(
+            "/campaigns/ending-soon",
+            get(handlers::get_ending_soon_campaigns),
+        )
+        .route("/campaigns/:id", get(handlers::get_campaign_detail))
+        .route("/campaigns/:id/events", get(handlers::get_campaign_events))
+        .route(
+            "/campaigns/:id/progress",
+            get(handlers::get_campaign_progress),
+        )
+        .route("/campaigns/:id/top-donors", get(handlers::get_top_donors))
+        .route(
+            "/campaigns/:id/refund-status",
+            get(handlers::get_refund_status),
+        )
+        .route("/donors/:pubkey/history", get(handlers::get_donor_history))
+        .route("/metadata/:id", get(handlers::get_hosted_metadata))
+        .route(
+            "/rpc",
+            post(handlers::proxy_rpc)
+                .route_layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    rate_limit::rate_limit,
+                ))
+                .layer(DefaultBodyLimit::max(handlers::MAX_RPC_PROXY_BODY_BYTES)),
+        )
+}
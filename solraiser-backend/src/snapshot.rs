@@ -0,0 +1,318 @@
+use std::{sync::Arc, time::Duration};
+
+use anyhow::{Context, Result};
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig, UiAccountEncoding},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_sdk::pubkey::Pubkey;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+
+use crate::{
+    accounts::{account_discriminator, CampaignAccount},
+    indexer::symbol_to_string,
+    state::AppState,
+};
+
+/// One row of the `campaigns` table
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CampaignSnapshot {
+    pub pubkey: String,
+    pub campaign_id: i64,
+    pub creator_pubkey: String,
+    pub goal_amount: i64,
+    pub amount_raised: i64,
+    pub deadline: i64,
+    pub metadata_url: String,
+    pub is_withdrawn: bool,
+    pub withdrawn_amount: i64,
+    pub goal_reached_at: i64,
+    pub decimals: i16,
+    pub symbol: Option<String>,
+}
+
+impl CampaignSnapshot {
+    /// Decodes the raw data of the `Campaign` account at `pubkey`
+    pub fn from_account(pubkey: &Pubkey, data: &[u8]) -> Result<Self> {
+        let campaign = CampaignAccount::decode(data)?;
+
+        Ok(CampaignSnapshot {
+            pubkey: pubkey.to_string(),
+            campaign_id: campaign.campaign_id as i64,
+            creator_pubkey: campaign.creator_pubkey.to_string(),
+            goal_amount: campaign.goal_amount as i64,
+            amount_raised: campaign.amount_raised as i64,
+            deadline: campaign.deadline,
+            metadata_url: campaign.metadata_url,
+            is_withdrawn: campaign.is_withdrawn,
+            withdrawn_amount: campaign.withdrawn_amount as i64,
+            goal_reached_at: campaign.goal_reached_at,
+            decimals: campaign.decimals as i16,
+            symbol: symbol_to_string(&campaign.symbol),
+        })
+    }
+}
+
+/// Reads every `Campaign` account owned by the program and upserts it into
+/// `campaigns`. Accounts that fail to decode are skipped. Returns the number
+/// of campaigns stored.
+pub async fn snapshot_campaigns(state: &AppState) -> Result<u64> {
+    let config = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_base58_encoded(
+            0,
+            &account_discriminator("Campaign"),
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+
+    let rpc_client = state.rpc_client.clone();
+    let program_id = state.program_id;
+    let accounts = tokio::task::spawn_blocking(move || {
+        rpc_client.get_program_ui_accounts_with_config(&program_id, config)
+    })
+    .await
+    .context("Failed to spawn program accounts lookup")?
+    .context("Failed to fetch program accounts")?;
+
+    let mut tx = state
+        .db
+        .begin()
+        .await
+        .context("Failed to begin transaction")?;
+    let mut stored = 0;
+
+    for (pubkey, account) in &accounts {
+        let decoded = account
+            .data
+            .decode()
+            .context("Account data is not binary-encoded")
+            .and_then(|data| CampaignSnapshot::from_account(pubkey, &data));
+        let snapshot = match decoded {
+            Ok(snapshot) => snapshot,
+            Err(e) => {
+                warn!("Skipping campaign account {}: {:?}", pubkey, e);
+                continue;
+            }
+        };
+
+        sqlx::query!(
+            r#"
+            INSERT INTO campaigns (
+                pubkey, campaign_id, creator_pubkey, goal_amount, amount_raised, deadline,
+                metadata_url, is_withdrawn, withdrawn_amount, goal_reached_at, decimals, symbol
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            ON CONFLICT (pubkey) DO UPDATE SET
+                goal_amount = EXCLUDED.goal_amount,
+                amount_raised = EXCLUDED.amount_raised,
+                deadline = EXCLUDED.deadline,
+                metadata_url = EXCLUDED.metadata_url,
+                is_withdrawn = EXCLUDED.is_withdrawn,
+                withdrawn_amount = EXCLUDED.withdrawn_amount,
+                goal_reached_at = EXCLUDED.goal_reached_at,
+                decimals = EXCLUDED.decimals,
+                symbol = EXCLUDED.symbol,
+                snapshot_at = CURRENT_TIMESTAMP
+            "#,
+            snapshot.pubkey,
+            snapshot.campaign_id,
+            snapshot.creator_pubkey,
+            snapshot.goal_amount,
+            snapshot.amount_raised,
+            snapshot.deadline,
+            snapshot.metadata_url,
+            snapshot.is_withdrawn,
+            snapshot.withdrawn_amount,
+            snapshot.goal_reached_at,
+            snapshot.decimals,
+            snapshot.symbol,
+        )
+        .execute(&mut *tx)
+        .await
+        .context("Failed to upsert campaign snapshot")?;
+        stored += 1;
+    }
+
+    tx.commit()
+        .await
+        .context("Failed to commit campaign snapshot")?;
+
+    info!(
+        "Snapshotted {} of {} campaign accounts",
+        stored,
+        accounts.len()
+    );
+    Ok(stored)
+}
+
+/// Runs [`snapshot_campaigns`] every `interval`
+pub fn spawn_snapshotter(state: Arc<AppState>, interval: Duration) -> JoinHandle<()> {
+    info!("Campaign snapshotter running every {:?}", interval);
+
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = snapshot_campaigns(&state).await {
+                error!("Campaign snapshot error: {:?}", e);
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::accounts::CAMPAIGN_ACCOUNT_LEN;
+    use base64::Engine;
+    use borsh::BorshSerialize;
+    use solana_client::{
+        rpc_client::{Mocks, RpcClient},
+        rpc_request::RpcRequest,
+    };
+    use sqlx::PgPool;
+    use std::sync::RwLock;
+
+    /// Raw account data as the program allocates it: discriminator, fields,
+    /// then zero padding up to `Campaign::LEN`
+    fn campaign_account_data(creator: &Pubkey, campaign_id: u64, amount_raised: u64) -> Vec<u8> {
+        #[derive(BorshSerialize)]
+        struct Fields<'a> {
+            creator_pubkey: [u8; 32],
+            campaign_id: u64,
+            goal_amount: u64,
+            amount_raised: u64,
+            deadline: i64,
+            metadata_url: &'a str,
+            is_withdrawn: bool,
+            withdrawn_amount: u64,
+            goal_reached_at: i64,
+            withdraw_cooldown_secs: u64,
+            last_withdraw_at: i64,
+            decimals: u8,
+            symbol: [u8; 8],
+            has_beneficiaries: bool,
+        }
+
+        let mut data = account_discriminator("Campaign").to_vec();
+        data.extend(
+            borsh::to_vec(&Fields {
+                creator_pubkey: creator.to_bytes(),
+                campaign_id,
+                goal_amount: 5_000,
+                amount_raised,
+                deadline: 1_700_086_400,
+                metadata_url: "https://example.com/campaign.json",
+                is_withdrawn: true,
+                withdrawn_amount: 4_000,
+                goal_reached_at: 1_700_050_000,
+                withdraw_cooldown_secs: 3_600,
+                last_withdraw_at: 1_700_090_000,
+                decimals: 9,
+                symbol: *b"SOL\0\0\0\0\0",
+                has_beneficiaries: false,
+            })
+            .unwrap(),
+        );
+        data.resize(CAMPAIGN_ACCOUNT_LEN, 0);
+        data
+    }
+
+    #[test]
+    fn raw_campaign_account_decodes_into_snapshot_row() {
+        let pubkey = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+
+        let snapshot =
+            CampaignSnapshot::from_account(&pubkey, &campaign_account_data(&creator, 7, 6_000))
+                .unwrap();
+
+        assert_eq!(
+            snapshot,
+            CampaignSnapshot {
+                pubkey: pubkey.to_string(),
+                campaign_id: 7,
+                creator_pubkey: creator.to_string(),
+                goal_amount: 5_000,
+                amount_raised: 6_000,
+                deadline: 1_700_086_400,
+                metadata_url: "https://example.com/campaign.json".to_string(),
+                is_withdrawn: true,
+                withdrawn_amount: 4_000,
+                goal_reached_at: 1_700_050_000,
+                decimals: 9,
+                symbol: Some("SOL".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn other_accounts_are_rejected() {
+        let mut data = campaign_account_data(&Pubkey::new_unique(), 7, 6_000);
+        data[0] ^= 0xff;
+        assert!(CampaignSnapshot::from_account(&Pubkey::new_unique(), &data).is_err());
+    }
+
+    #[sqlx::test]
+    async fn snapshot_upserts_decodable_campaign_accounts(pool: PgPool) {
+        let program_id = Pubkey::new_unique();
+        let creator = Pubkey::new_unique();
+        let campaign = Pubkey::new_unique();
+        let keyed_account = |pubkey: &Pubkey, data: &[u8]| {
+            serde_json::json!({
+                "pubkey": pubkey.to_string(),
+                "account": {
+                    "lamports": 1_000_000,
+                    "data": [base64::engine::general_purpose::STANDARD.encode(data), "base64"],
+                    "owner": program_id.to_string(),
+                    "executable": false,
+                    "rentEpoch": 0,
+                    "space": data.len(),
+                },
+            })
+        };
+
+        let state_with = |amount_raised: u64| {
+            let mocks = Mocks::from([(
+                RpcRequest::GetProgramAccounts,
+                serde_json::json!([
+                    keyed_account(
+                        &campaign,
+                        &campaign_account_data(&creator, 7, amount_raised)
+                    ),
+                    keyed_account(&Pubkey::new_unique(), &[0; 16]),
+                ]),
+            )]);
+            AppState {
+                db: pool.clone(),
+                rpc_client: Arc::new(RpcClient::new_mock_with_mocks("succeeds", mocks)),
+                program_id,
+                last_indexed_slot: Arc::new(RwLock::new(0)),
+                pipeline_metrics: Arc::default(),
+                idempotency: Arc::default(),
+                campaign_rent: Arc::default(),
+                creator_stats_rent: Arc::default(),
+                rpc_rate_limiter: Arc::default(),
+                metadata_fetcher: Arc::default(),
+                public_url: None,
+            }
+        };
+
+        assert_eq!(snapshot_campaigns(&state_with(6_000)).await.unwrap(), 1);
+        // A later snapshot overwrites the row rather than adding one
+        assert_eq!(snapshot_campaigns(&state_with(9_000)).await.unwrap(), 1);
+
+        let rows: Vec<(String, i64, i64, bool)> = sqlx::query_as(
+            "SELECT pubkey, campaign_id, amount_raised, is_withdrawn FROM campaigns",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap();
+        assert_eq!(rows, vec![(campaign.to_string(), 7, 9_000, true)]);
+    }
+}
@@ -1,22 +1,50 @@
-use std::sync::{Arc, RwLock};
+use std::sync::{Arc, OnceLock, RwLock};
 
 use solana_client::rpc_client::RpcClient;
+use solana_sdk::pubkey::Pubkey;
 use sqlx::PgPool;
 
+use crate::{
+    idempotency::IdempotencyCache, indexer::PipelineMetrics, metadata::MetadataFetcher,
+    rate_limit::RateLimiter,
+};
+
 #[derive(Clone)]
 pub struct AppState {
     pub db: PgPool,
     pub rpc_client: Arc<RpcClient>,
+    pub program_id: Pubkey,
     pub last_indexed_slot: Arc<RwLock<u64>>,
+    pub pipeline_metrics: Arc<PipelineMetrics>,
+    pub idempotency: Arc<IdempotencyCache>,
+    /// Rent-exempt minimum of a campaign account; fetched once, as it only
+    /// changes if the cluster's rent parameters do
+    pub campaign_rent: Arc<OnceLock<u64>>,
+    /// Rent-exempt minimum of a creator stats account, cached like `campaign_rent`
+    pub creator_stats_rent: Arc<OnceLock<u64>>,
+    /// Requests per client IP allowed through the `/rpc` proxy
+    pub rpc_rate_limiter: Arc<RateLimiter>,
+    pub metadata_fetcher: Arc<MetadataFetcher>,
+    /// Base URL the metadata documents `build-create-tx` hosts are linked
+    /// under; without it, that endpoint can't build transactions
+    pub public_url: Option<Arc<str>>,
 }
 
 impl AppState {
-    pub fn new(db: PgPool, rpc_url: String, start_slot: u64) -> Self {
+    pub fn new(db: PgPool, rpc_url: String, program_id: Pubkey, start_slot: u64) -> Self {
         AppState {
             db,
             rpc_client: Arc::new(RpcClient::new(rpc_url)),
+            program_id,
             // RwLock for multiple reads and only single write at a time
             last_indexed_slot: Arc::new(RwLock::new(start_slot)),
+            pipeline_metrics: Arc::default(),
+            idempotency: Arc::default(),
+            campaign_rent: Arc::default(),
+            creator_stats_rent: Arc::default(),
+            rpc_rate_limiter: Arc::default(),
+            metadata_fetcher: Arc::default(),
+            public_url: None,
         }
     }
 }
@@ -0,0 +1,76 @@
+mod common;
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+
+async fn seed(pool: &PgPool) {
+    for slot in [10, 11, 13, 20] {
+        common::insert_block(pool, slot, Some(1_700_000_000 + slot)).await;
+    }
+}
+
+fn slots(body: &serde_json::Value) -> Vec<i64> {
+    body.as_array()
+        .unwrap()
+        .iter()
+        .map(|b| b["slot"].as_i64().unwrap())
+        .collect()
+}
+
+#[sqlx::test]
+async fn single_block_is_returned_by_slot(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) = common::get_json(common::app_state(pool), "/v1/blocks/13").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["slot"], 13);
+    assert_eq!(body["blockhash"], "blockhash-13");
+    assert_eq!(body["block_time"], 1_700_000_013);
+}
+
+#[sqlx::test]
+async fn unindexed_slot_is_not_found(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, _) = common::get_json(common::app_state(pool), "/v1/blocks/12").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn range_is_inclusive_and_ascending(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) = common::get_json(
+        common::app_state(pool.clone()),
+        "/v1/blocks?from_slot=11&to_slot=20",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(slots(&body), vec![11, 13, 20]);
+
+    let (_, body) = common::get_json(
+        common::app_state(pool.clone()),
+        "/v1/blocks?from_slot=11&limit=2",
+    )
+    .await;
+    assert_eq!(slots(&body), vec![11, 13]);
+
+    let (status, _) = common::get_json(
+        common::app_state(pool),
+        "/v1/blocks?from_slot=20&to_slot=10",
+    )
+    .await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
+
+#[sqlx::test]
+async fn recent_blocks_are_newest_first(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) =
+        common::get_json(common::app_state(pool), "/v1/blocks/recent?limit=2").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(slots(&body), vec![20, 13]);
+}
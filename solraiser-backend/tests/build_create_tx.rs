@@ -0,0 +1,215 @@
+mod common;
+
+use axum::http::StatusCode;
+use base64::Engine;
+use solana_client::{rpc_client::Mocks, rpc_request::RpcRequest};
+use solana_sdk::{hash::Hash, pubkey::Pubkey, transaction::Transaction};
+use solraiser_backend::{accounts, instructions, models::FeeEstimate, pda};
+use sqlx::PgPool;
+
+fn request(creator: &Pubkey, target_amount: u64, duration: u64) -> serde_json::Value {
+    serde_json::json!({
+        "target_amount": target_amount,
+        "duration": duration,
+        "creator": creator.to_string(),
+        "name": "Solar panels for the school",
+        "description": "Help us install solar panels on the village school roof",
+        "image_url": "https://example.com/school.png",
+    })
+}
+
+/// `getAccountInfo` result for a registry whose next id is `next_id`
+fn registry_account(next_id: u64) -> serde_json::Value {
+    let mut data = accounts::account_discriminator("Registry").to_vec();
+    data.extend(next_id.to_le_bytes());
+    data.push(255);
+    data.extend(Pubkey::new_unique().to_bytes());
+    data.extend(0u32.to_le_bytes());
+
+    serde_json::json!({
+        "context": { "slot": 1 },
+        "value": {
+            "lamports": 1_000_000,
+            "data": [base64::engine::general_purpose::STANDARD.encode(&data), "base64"],
+            "owner": Pubkey::new_unique().to_string(),
+            "executable": false,
+            "rentEpoch": 0,
+            "space": data.len(),
+        },
+    })
+}
+
+#[sqlx::test]
+async fn returns_unsigned_create_campaign_transaction(pool: PgPool) {
+    let blockhash = Hash::new_unique();
+    let mocks = Mocks::from([
+        (
+            RpcRequest::GetLatestBlockhash,
+            serde_json::json!({
+                "context": { "slot": 1 },
+                "value": { "blockhash": blockhash.to_string(), "lastValidBlockHeight": 150 },
+            }),
+        ),
+        (RpcRequest::GetAccountInfo, registry_account(42)),
+        (
+            RpcRequest::GetRecentPrioritizationFees,
+            serde_json::json!([{ "slot": 100, "prioritizationFee": 250 }]),
+        ),
+    ]);
+    let state = common::mock_app_state(pool, mocks);
+    let program_id = state.program_id;
+    let creator = Pubkey::new_unique();
+
+    let (status, body) = common::post_json(
+        state.clone(),
+        "/v1/campaigns/build-create-tx",
+        request(&creator, 1_000_000_000, 86_400),
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(body["transaction"].as_str().unwrap())
+        .unwrap();
+    let transaction: Transaction = bincode::deserialize(&bytes).unwrap();
+    let message = &transaction.message;
+
+    let (pda, _) = pda::derive_campaign_pda(&program_id, &creator, 42);
+    assert_eq!(body["campaign_pda"], pda.to_string());
+    assert_eq!(body["campaign_id"], 42);
+    assert_eq!(message.recent_blockhash, blockhash);
+    assert!(transaction
+        .signatures
+        .iter()
+        .all(|s| *s == Default::default()));
+
+    // Creator pays the fee, so it must be the first account
+    assert_eq!(message.account_keys[0], creator);
+    assert_eq!(message.header.num_required_signatures, 1);
+
+    // Compute budget first, at the fee `/campaigns/fee-estimate` quotes
+    assert_eq!(message.instructions.len(), 3);
+    for (instruction, expected) in message.instructions[..2].iter().zip([
+        instructions::set_compute_unit_limit(FeeEstimate::CREATE_CAMPAIGN_COMPUTE_UNITS),
+        instructions::set_compute_unit_price(250),
+    ]) {
+        assert_eq!(
+            message.account_keys[instruction.program_id_index as usize],
+            instructions::COMPUTE_BUDGET_PROGRAM_ID
+        );
+        assert_eq!(instruction.data, expected.data);
+    }
+
+    let instruction = &message.instructions[2];
+    assert_eq!(
+        message.account_keys[instruction.program_id_index as usize],
+        program_id
+    );
+
+    let accounts: Vec<Pubkey> = instruction
+        .accounts
+        .iter()
+        .map(|&index| message.account_keys[index as usize])
+        .collect();
+    assert_eq!(
+        accounts,
+        vec![
+            pda::derive_registry_pda(&program_id).0,
+            pda,
+            pda::derive_creator_stats_pda(&program_id, &creator).0,
+            // No beneficiaries: the optional account is the program id
+            program_id,
+            creator,
+            solana_system_interface::program::ID
+        ]
+    );
+    assert_eq!(
+        instruction.data[..8],
+        instructions::instruction_discriminator("create_campaign")
+    );
+
+    // The campaign's metadata_url is a document the API serves
+    let metadata_url = body["metadata_url"].as_str().unwrap();
+    assert!(String::from_utf8_lossy(&instruction.data).contains(metadata_url));
+    let path = metadata_url.strip_prefix(common::PUBLIC_URL).unwrap();
+    let (status, metadata) = common::get_json(state, path).await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        metadata,
+        serde_json::json!({
+            "name": "Solar panels for the school",
+            "description": "Help us install solar panels on the village school roof",
+            "image_url": "https://example.com/school.png",
+        })
+    );
+}
+
+#[sqlx::test]
+async fn rejects_missing_name_and_unfetchable_image(pool: PgPool) {
+    let creator = Pubkey::new_unique();
+
+    for (field, value, error) in [
+        ("name", " ", "name must not be empty"),
+        (
+            "image_url",
+            "school.png",
+            "image_url must be an http(s) or ipfs URL",
+        ),
+    ] {
+        let mut body = request(&creator, 1_000_000_000, 86_400);
+        body[field] = value.into();
+
+        let (status, body) = common::post_json(
+            common::app_state(pool.clone()),
+            "/v1/campaigns/build-create-tx",
+            body,
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert_eq!(body["error"], error);
+    }
+}
+
+#[sqlx::test]
+async fn unknown_metadata_is_not_found(pool: PgPool) {
+    let (status, _) = common::get_json(common::app_state(pool), "/v1/metadata/unknown").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
+
+#[sqlx::test]
+async fn rejects_zero_target_and_duration(pool: PgPool) {
+    let creator = Pubkey::new_unique();
+
+    for (target_amount, duration) in [(0, 86_400), (1_000, 0)] {
+        let (status, body) = common::post_json(
+            common::app_state(pool.clone()),
+            "/v1/campaigns/build-create-tx",
+            request(&creator, target_amount, duration),
+        )
+        .await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+        assert!(body["error"].as_str().unwrap().contains("greater than 0"));
+    }
+}
+
+#[sqlx::test]
+async fn rejects_goal_below_the_programs_minimum(pool: PgPool) {
+    let mocks = Mocks::from([(
+        RpcRequest::GetMinimumBalanceForRentExemption,
+        serde_json::json!(3_500_000),
+    )]);
+    let min_goal = 3_500_000 + accounts::MIN_GOAL_ABOVE_RENT;
+
+    let (status, body) = common::post_json(
+        common::mock_app_state(pool, mocks),
+        "/v1/campaigns/build-create-tx",
+        request(&Pubkey::new_unique(), min_goal - 1, 86_400),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"]
+        .as_str()
+        .unwrap()
+        .contains(&min_goal.to_string()));
+}
@@ -0,0 +1,65 @@
+mod common;
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+
+async fn seed(pool: &PgPool) {
+    common::insert_campaign(
+        pool,
+        1,
+        1_000,
+        4_000_000_000,
+        "Solar panels for the school",
+        "Help us install solar panels on the village school roof",
+    )
+    .await;
+    common::insert_event(pool, "donate-1", 10, "donated", 1, "alice", Some(300)).await;
+    common::insert_event(pool, "donate-2", 20, "donated", 1, "bob", Some(450)).await;
+}
+
+#[sqlx::test]
+async fn detail_combines_summary_metadata_and_recent_events(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) = common::get_json(common::app_state(pool), "/v1/campaigns/1").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["campaign_id"], 1);
+    assert_eq!(body["creator_pubkey"], "creator");
+    assert_eq!(body["goal_amount"], 1_000);
+    assert_eq!(body["amount_raised"], 750);
+    assert_eq!(body["deadline"], 4_000_000_000_i64);
+    assert_eq!(body["status"], "active");
+    assert_eq!(body["metadata"]["name"], "Solar panels for the school");
+    assert_eq!(
+        body["metadata"]["description"],
+        "Help us install solar panels on the village school roof"
+    );
+
+    let events: Vec<&str> = body["recent_events"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["signature"].as_str().unwrap())
+        .collect();
+    assert_eq!(events, vec!["donate-2", "donate-1", "create-1"]);
+}
+
+#[sqlx::test]
+async fn events_param_limits_recent_events(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) =
+        common::get_json(common::app_state(pool), "/v1/campaigns/1?events=1").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let events = body["recent_events"].as_array().unwrap();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0]["signature"], "donate-2");
+}
+
+#[sqlx::test]
+async fn unknown_campaign_is_not_found(pool: PgPool) {
+    let (status, _) = common::get_json(common::app_state(pool), "/v1/campaigns/99").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
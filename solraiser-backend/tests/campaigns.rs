@@ -0,0 +1,92 @@
+mod common;
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+
+async fn seed(pool: &PgPool, count: i64) {
+    for id in 1..=count {
+        common::insert_campaign(
+            pool,
+            id,
+            1_000,
+            2_000_000_000,
+            &format!("Campaign {id}"),
+            "",
+        )
+        .await;
+    }
+}
+
+fn ids(campaigns: &[serde_json::Value]) -> Vec<i64> {
+    campaigns
+        .iter()
+        .map(|c| c["campaign_id"].as_i64().unwrap())
+        .collect()
+}
+
+#[sqlx::test]
+async fn list_is_paginated(pool: PgPool) {
+    seed(&pool, 5).await;
+
+    let (status, body) =
+        common::get_json(common::app_state(pool), "/v1/campaigns?limit=2&offset=2").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(body.as_array().unwrap()), vec![3, 4]);
+}
+
+#[sqlx::test]
+async fn stream_emits_one_line_per_campaign(pool: PgPool) {
+    // More than both the list page size and the stream's read-ahead buffer
+    seed(&pool, 150).await;
+    common::insert_event(&pool, "donate-7", 700, "donated", 7, "donor", Some(250)).await;
+
+    let (status, rows) = common::get_ndjson(common::app_state(pool), "/v1/campaigns/stream").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(rows.len(), 150);
+    assert_eq!(ids(&rows), (1..=150).collect::<Vec<_>>());
+    assert_eq!(rows[6]["amount_raised"], 250);
+}
+
+#[sqlx::test]
+async fn stream_of_no_campaigns_is_empty(pool: PgPool) {
+    let (status, rows) = common::get_ndjson(common::app_state(pool), "/v1/campaigns/stream").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert!(rows.is_empty());
+}
+
+#[sqlx::test]
+async fn last_event_slot_advances_with_new_events(pool: PgPool) {
+    seed(&pool, 1).await;
+    let state = common::app_state(pool.clone());
+
+    let (_, body) = common::get_json(state.clone(), "/v1/campaigns/1").await;
+    assert_eq!(body["last_event_slot"], 1);
+    assert_eq!(body["updated_at"], "2023-11-14T22:13:21Z");
+
+    common::insert_event(&pool, "donate-1", 20, "donated", 1, "donor", Some(300)).await;
+
+    let (_, body) = common::get_json(state.clone(), "/v1/campaigns/1").await;
+    assert_eq!(body["last_event_slot"], 20);
+    assert_eq!(body["updated_at"], "2023-11-14T22:13:40Z");
+
+    let (_, body) = common::get_json(state, "/v1/campaigns").await;
+    assert_eq!(body[0]["last_event_slot"], 20);
+}
+
+#[sqlx::test]
+async fn summaries_report_progress_capped_at_full(pool: PgPool) {
+    seed(&pool, 2).await;
+    common::insert_event(&pool, "donate-1", 10, "donated", 1, "donor", Some(333)).await;
+    common::insert_event(&pool, "donate-2", 20, "donated", 2, "donor", Some(2_500)).await;
+
+    let (status, body) = common::get_json(common::app_state(pool), "/v1/campaigns").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body[0]["progress_bps"], 3_330);
+    assert_eq!(body[0]["is_goal_reached"], false);
+    assert_eq!(body[1]["progress_bps"], 10_000);
+    assert_eq!(body[1]["is_goal_reached"], true);
+}
@@ -0,0 +1,196 @@
+#![allow(dead_code)]
+
+use axum::{
+    body::{to_bytes, Body},
+    http::{Request, StatusCode},
+};
+use solana_client::rpc_client::{Mocks, RpcClient};
+use solana_sdk::pubkey::Pubkey;
+use solraiser_backend::{routes, state::AppState};
+use sqlx::PgPool;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// `PUBLIC_URL` of [`app_state`]
+pub const PUBLIC_URL: &str = "https://api.solraiser.test";
+
+pub fn app_state(pool: PgPool) -> AppState {
+    AppState {
+        public_url: Some(Arc::from(PUBLIC_URL)),
+        ..AppState::new(
+            pool,
+            "http://127.0.0.1:8899".to_string(),
+            Pubkey::new_unique(),
+            0,
+        )
+    }
+}
+
+/// Like [`app_state`], but RPC calls are answered from `mocks`
+pub fn mock_app_state(pool: PgPool, mocks: Mocks) -> AppState {
+    AppState {
+        rpc_client: Arc::new(RpcClient::new_mock_with_mocks("succeeds", mocks)),
+        ..app_state(pool)
+    }
+}
+
+/// Sends a GET through the full router and decodes the JSON body
+pub async fn get_json(state: AppState, uri: &str) -> (StatusCode, serde_json::Value) {
+    send(state, Request::get(uri).body(Body::empty()).unwrap()).await
+}
+
+/// Sends a JSON POST through the full router and decodes the JSON body
+pub async fn post_json(
+    state: AppState,
+    uri: &str,
+    body: serde_json::Value,
+) -> (StatusCode, serde_json::Value) {
+    post_json_with_headers(state, uri, body, &[]).await
+}
+
+/// Like [`post_json`], with extra request headers
+pub async fn post_json_with_headers(
+    state: AppState,
+    uri: &str,
+    body: serde_json::Value,
+    headers: &[(&str, &str)],
+) -> (StatusCode, serde_json::Value) {
+    let mut request = Request::post(uri).header("content-type", "application/json");
+    for (name, value) in headers {
+        request = request.header(*name, *value);
+    }
+    send(state, request.body(Body::from(body.to_string())).unwrap()).await
+}
+
+/// Sends a GET through the full router and decodes each line of an NDJSON body
+pub async fn get_ndjson(state: AppState, uri: &str) -> (StatusCode, Vec<serde_json::Value>) {
+    let request = Request::get(uri).body(Body::empty()).unwrap();
+    let response = routes::router(state).oneshot(request).await.unwrap();
+
+    let status = response.status();
+    assert_eq!(
+        response.headers()["content-type"],
+        "application/x-ndjson",
+        "not an NDJSON response"
+    );
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let rows = String::from_utf8(bytes.to_vec())
+        .unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+
+    (status, rows)
+}
+
+async fn send(state: AppState, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+    let response = routes::router(state).oneshot(request).await.unwrap();
+
+    let status = response.status();
+    let bytes = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    let body = if bytes.is_empty() {
+        serde_json::Value::Null
+    } else {
+        serde_json::from_slice(&bytes).unwrap()
+    };
+
+    (status, body)
+}
+
+pub async fn insert_block(pool: &PgPool, slot: i64, block_time: Option<i64>) {
+    sqlx::query(
+        "INSERT INTO blocks (slot, blockhash, block_time) VALUES ($1, $2, $3)
+         ON CONFLICT (slot) DO NOTHING",
+    )
+    .bind(slot)
+    .bind(format!("blockhash-{slot}"))
+    .bind(block_time)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+/// Inserts the block, transaction and `campaign_events` row for one event
+pub async fn insert_event(
+    pool: &PgPool,
+    signature: &str,
+    slot: i64,
+    event_type: &str,
+    campaign_id: i64,
+    user_pubkey: &str,
+    amount: Option<i64>,
+) {
+    insert_block(pool, slot, Some(1_700_000_000 + slot)).await;
+
+    sqlx::query(
+        "INSERT INTO transactions (signature, slot, block_time, success, fee)
+         VALUES ($1, $2, $3, true, 5000)
+         ON CONFLICT (signature) DO NOTHING",
+    )
+    .bind(signature)
+    .bind(slot)
+    .bind(1_700_000_000 + slot)
+    .execute(pool)
+    .await
+    .unwrap();
+
+    sqlx::query(
+        "INSERT INTO campaign_events (signature, slot, event_type, campaign_id, user_pubkey, amount)
+         VALUES ($1, $2, $3, $4, $5, $6)",
+    )
+    .bind(signature)
+    .bind(slot)
+    .bind(event_type)
+    .bind(campaign_id)
+    .bind(user_pubkey)
+    .bind(amount)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+/// Inserts a `created` event for a campaign along with its cached metadata
+pub async fn insert_campaign(
+    pool: &PgPool,
+    campaign_id: i64,
+    goal_amount: i64,
+    deadline: i64,
+    name: &str,
+    description: &str,
+) {
+    let signature = format!("create-{campaign_id}");
+    insert_event(
+        pool,
+        &signature,
+        campaign_id,
+        "created",
+        campaign_id,
+        "creator",
+        None,
+    )
+    .await;
+
+    sqlx::query(
+        "UPDATE campaign_events SET goal_amount = $2, deadline = $3, metadata_url = $4
+         WHERE signature = $1",
+    )
+    .bind(&signature)
+    .bind(goal_amount)
+    .bind(deadline)
+    .bind(format!("https://example.com/{campaign_id}.json"))
+    .execute(pool)
+    .await
+    .unwrap();
+
+    sqlx::query(
+        "INSERT INTO campaign_metadata (campaign_id, metadata_url, name, description)
+         VALUES ($1, $2, $3, $4)",
+    )
+    .bind(campaign_id)
+    .bind(format!("https://example.com/{campaign_id}.json"))
+    .bind(name)
+    .bind(description)
+    .execute(pool)
+    .await
+    .unwrap();
+}
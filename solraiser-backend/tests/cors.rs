@@ -0,0 +1,57 @@
+mod common;
+
+use axum::{
+    body::Body,
+    http::{header, Method, Request},
+};
+use solraiser_backend::{config::AllowedOrigins, routes};
+use sqlx::PgPool;
+use tower::ServiceExt;
+
+async fn preflight(pool: PgPool, allowed_origins: &AllowedOrigins, origin: &str) -> Option<String> {
+    let app =
+        routes::router(common::app_state(pool)).layer(routes::cors_layer(allowed_origins).unwrap());
+
+    let response = app
+        .oneshot(
+            Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/v1/campaigns/build-create-tx")
+                .header(header::ORIGIN, origin)
+                .header(header::ACCESS_CONTROL_REQUEST_METHOD, "POST")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+
+    response
+        .headers()
+        .get(header::ACCESS_CONTROL_ALLOW_ORIGIN)
+        .map(|value| value.to_str().unwrap().to_string())
+}
+
+#[sqlx::test]
+async fn listed_origin_is_allowed(pool: PgPool) {
+    let allowed = AllowedOrigins::parse("https://solraiser.app").unwrap();
+
+    let allow_origin = preflight(pool, &allowed, "https://solraiser.app").await;
+
+    assert_eq!(allow_origin.as_deref(), Some("https://solraiser.app"));
+}
+
+#[sqlx::test]
+async fn unlisted_origin_is_rejected(pool: PgPool) {
+    let allowed = AllowedOrigins::parse("https://solraiser.app").unwrap();
+
+    let allow_origin = preflight(pool, &allowed, "https://evil.example").await;
+
+    assert_eq!(allow_origin, None);
+}
+
+#[sqlx::test]
+async fn wildcard_allows_any_origin(pool: PgPool) {
+    let allow_origin = preflight(pool, &AllowedOrigins::Any, "https://evil.example").await;
+
+    assert_eq!(allow_origin.as_deref(), Some("*"));
+}
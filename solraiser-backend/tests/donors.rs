@@ -0,0 +1,79 @@
+mod common;
+
+use axum::http::StatusCode;
+use solana_sdk::pubkey::Pubkey;
+use sqlx::PgPool;
+
+async fn seed(pool: &PgPool, donor: &str) {
+    common::insert_campaign(pool, 1, 1_000, 1_900_000_000, "Community garden", "Tools").await;
+    common::insert_campaign(pool, 2, 5_000, 1_900_000_000, "School roof", "Solar").await;
+    common::insert_event(pool, "donate-a", 10, "donated", 1, donor, Some(100)).await;
+    common::insert_event(pool, "donate-b", 20, "donated", 2, donor, Some(250)).await;
+    common::insert_event(pool, "donate-c", 30, "donated", 1, donor, Some(50)).await;
+    common::insert_event(pool, "someone-else", 40, "donated", 1, "other", Some(999)).await;
+}
+
+#[sqlx::test]
+async fn history_spans_campaigns_newest_first(pool: PgPool) {
+    let donor = Pubkey::new_unique().to_string();
+    seed(&pool, &donor).await;
+
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        &format!("/v1/donors/{donor}/history?limit=2"),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_amount"], 400);
+    assert_eq!(body["donation_count"], 3);
+
+    let donations = body["donations"].as_array().unwrap();
+    assert_eq!(donations.len(), 2);
+    assert_eq!(donations[0]["signature"], "donate-c");
+    assert_eq!(donations[0]["campaign_name"], "Community garden");
+    assert_eq!(donations[0]["block_time"], 1_700_000_030);
+    assert_eq!(donations[1]["signature"], "donate-b");
+    assert_eq!(donations[1]["campaign_name"], "School roof");
+    assert_eq!(donations[1]["amount"], 250);
+}
+
+#[sqlx::test]
+async fn history_can_be_narrowed_to_one_campaign(pool: PgPool) {
+    let donor = Pubkey::new_unique().to_string();
+    seed(&pool, &donor).await;
+
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        &format!("/v1/donors/{donor}/history?campaign_id=2"),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_amount"], 250);
+    assert_eq!(body["donations"].as_array().unwrap().len(), 1);
+}
+
+#[sqlx::test]
+async fn donor_without_history_gets_empty_list(pool: PgPool) {
+    let donor = Pubkey::new_unique().to_string();
+
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        &format!("/v1/donors/{donor}/history"),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["total_amount"], 0);
+    assert_eq!(body["donation_count"], 0);
+    assert_eq!(body["donations"], serde_json::json!([]));
+}
+
+#[sqlx::test]
+async fn malformed_donor_pubkey_is_rejected(pool: PgPool) {
+    let (status, _) =
+        common::get_json(common::app_state(pool), "/v1/donors/not-a-pubkey/history").await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,130 @@
+mod common;
+
+use axum::http::StatusCode;
+use solraiser_backend::expiry::sweep_campaign_statuses;
+use sqlx::PgPool;
+
+const HOUR: i64 = 3600;
+
+async fn seed(pool: &PgPool) {
+    let now = chrono::Utc::now().timestamp();
+
+    common::insert_campaign(pool, 1, 1_000, now + 10 * HOUR, "Ten hours", "").await;
+    common::insert_campaign(pool, 2, 1_000, now + 2 * HOUR, "Two hours", "").await;
+    common::insert_campaign(pool, 3, 1_000, now - HOUR, "Expired", "").await;
+    common::insert_campaign(pool, 4, 1_000, now + 50 * HOUR, "Fifty hours", "").await;
+    common::insert_campaign(pool, 5, 1_000, now + HOUR, "Withdrawn", "").await;
+    common::insert_event(pool, "donate-2", 20, "donated", 2, "donor", Some(900)).await;
+    common::insert_event(
+        pool,
+        "withdraw-5",
+        50,
+        "withdrawn",
+        5,
+        "creator",
+        Some(1_000),
+    )
+    .await;
+    sweep_campaign_statuses(pool, now).await.unwrap();
+}
+
+fn ids(body: &serde_json::Value) -> Vec<i64> {
+    body.as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["campaign_id"].as_i64().unwrap())
+        .collect()
+}
+
+#[sqlx::test]
+async fn active_campaigns_are_ordered_by_deadline(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) =
+        common::get_json(common::app_state(pool), "/v1/campaigns/ending-soon").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&body), vec![2, 1, 4]);
+    assert_eq!(body[0]["progress_bps"], 9_000);
+    assert_eq!(body[0]["is_goal_reached"], false);
+    assert_eq!(body[0]["name"], "Two hours");
+    assert_eq!(body[1]["progress_bps"], 0);
+
+    let remaining = body[0]["seconds_remaining"].as_i64().unwrap();
+    assert!(remaining > HOUR && remaining <= 2 * HOUR);
+}
+
+#[sqlx::test]
+async fn within_hours_limits_the_window(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        "/v1/campaigns/ending-soon?within_hours=24",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&body), vec![2, 1]);
+}
+
+#[sqlx::test]
+async fn zero_goal_counts_as_reached(pool: PgPool) {
+    let now = chrono::Utc::now().timestamp();
+    common::insert_campaign(&pool, 1, 0, now + HOUR, "No goal", "").await;
+
+    let (status, body) =
+        common::get_json(common::app_state(pool), "/v1/campaigns/ending-soon").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body[0]["progress_bps"], 10_000);
+    assert_eq!(body[0]["is_goal_reached"], true);
+}
+
+#[sqlx::test]
+async fn overfunded_campaign_reports_capped_progress(pool: PgPool) {
+    let now = chrono::Utc::now().timestamp();
+    common::insert_campaign(&pool, 1, 1_000, now + HOUR, "Overfunded", "").await;
+    common::insert_event(&pool, "donate-1", 10, "donated", 1, "donor", Some(2_500)).await;
+
+    let (status, body) =
+        common::get_json(common::app_state(pool), "/v1/campaigns/ending-soon").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body[0]["progress_bps"], 10_000);
+    assert_eq!(body[0]["is_goal_reached"], true);
+}
+
+#[sqlx::test]
+async fn huge_within_hours_does_not_overflow(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        &format!("/v1/campaigns/ending-soon?within_hours={}", i64::MAX),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&body), vec![2, 1, 4]);
+}
+
+#[sqlx::test]
+async fn expired_campaign_is_excluded_before_the_sweep(pool: PgPool) {
+    let now = chrono::Utc::now().timestamp();
+    common::insert_campaign(&pool, 1, 1_000, now + HOUR, "Ongoing", "").await;
+    sweep_campaign_statuses(&pool, now).await.unwrap();
+    // Expired after the last sweep, so still recorded as active
+    common::insert_campaign(&pool, 2, 1_000, now - 60, "Just expired", "").await;
+    sweep_campaign_statuses(&pool, now - 2 * HOUR)
+        .await
+        .unwrap();
+    // Never swept at all
+    common::insert_campaign(&pool, 3, 1_000, now - 60, "Unswept", "").await;
+
+    let (status, body) =
+        common::get_json(common::app_state(pool), "/v1/campaigns/ending-soon").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(ids(&body), vec![1]);
+}
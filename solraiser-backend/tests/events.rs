@@ -0,0 +1,136 @@
+mod common;
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+
+async fn seed(pool: &PgPool) {
+    common::insert_event(pool, "sig-10", 10, "donated", 1, "donor", Some(100)).await;
+    common::insert_event(pool, "sig-30", 30, "donated", 1, "donor", Some(300)).await;
+    common::insert_event(pool, "sig-20", 20, "donated", 1, "donor", Some(200)).await;
+    common::insert_event(pool, "sig-25", 25, "donated", 2, "donor", Some(250)).await;
+}
+
+fn slots(body: &serde_json::Value) -> Vec<i64> {
+    body["events"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|e| e["slot"].as_i64().unwrap())
+        .collect()
+}
+
+#[sqlx::test]
+async fn campaign_events_since_slot_are_newer_and_ascending(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        "/v1/campaigns/1/events?since_slot=10",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(slots(&body), vec![20, 30]);
+}
+
+#[sqlx::test]
+async fn global_events_since_slot_span_campaigns(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) =
+        common::get_json(common::app_state(pool), "/v1/events?since_slot=15").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(slots(&body), vec![20, 25, 30]);
+}
+
+#[sqlx::test]
+async fn events_limit_caps_page_size(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) =
+        common::get_json(common::app_state(pool), "/v1/events?since_slot=0&limit=2").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(slots(&body), vec![10, 20]);
+}
+
+#[sqlx::test]
+async fn raw_logs_are_only_returned_when_requested(pool: PgPool) {
+    seed(&pool).await;
+    sqlx::query(
+        "UPDATE campaign_events SET raw_logs = ARRAY['Program log: Instruction: Donate', 'Program data: AAAA']
+         WHERE signature = 'sig-10'",
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (status, body) = common::get_json(
+        common::app_state(pool.clone()),
+        "/v1/campaigns/1/events?limit=1",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body["events"][0].get("raw_logs").is_none());
+
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        "/v1/campaigns/1/events?limit=1&include_logs=true",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body["events"][0]["raw_logs"],
+        serde_json::json!(["Program log: Instruction: Donate", "Program data: AAAA"])
+    );
+}
+
+#[sqlx::test]
+async fn cursor_pages_through_a_slot_larger_than_the_page(pool: PgPool) {
+    for signature in ["sig-a", "sig-b", "sig-c"] {
+        common::insert_event(&pool, signature, 40, "donated", 1, "donor", Some(1)).await;
+    }
+    common::insert_event(&pool, "sig-d", 41, "donated", 1, "donor", Some(1)).await;
+
+    let mut signatures = Vec::new();
+    let mut uri = "/v1/campaigns/1/events?since_slot=39&limit=2".to_string();
+    loop {
+        let (status, body) = common::get_json(common::app_state(pool.clone()), &uri).await;
+        assert_eq!(status, StatusCode::OK);
+
+        let events = body["events"].as_array().unwrap();
+        if events.is_empty() {
+            break;
+        }
+        signatures.extend(
+            events
+                .iter()
+                .map(|e| e["signature"].as_str().unwrap().to_string()),
+        );
+        uri = format!(
+            "/v1/campaigns/1/events?since_slot={}&since_id={}&limit=2",
+            body["next_cursor"]["since_slot"], body["next_cursor"]["since_id"]
+        );
+    }
+
+    assert_eq!(signatures, vec!["sig-a", "sig-b", "sig-c", "sig-d"]);
+}
+
+#[sqlx::test]
+async fn empty_page_keeps_the_cursor(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        "/v1/events?since_slot=30&since_id=99",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(slots(&body), Vec::<i64>::new());
+    assert_eq!(
+        body["next_cursor"],
+        serde_json::json!({ "since_slot": 30, "since_id": 99 })
+    );
+}
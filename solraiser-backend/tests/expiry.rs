@@ -0,0 +1,130 @@
+mod common;
+
+use axum::http::StatusCode;
+use solraiser_backend::expiry::sweep_campaign_statuses;
+use sqlx::PgPool;
+
+const DEADLINE: i64 = 1_700_000_000;
+
+/// Campaign 1 raises 400 of 1000, campaign 2 raises 1500 of 1000
+async fn seed(pool: &PgPool) {
+    common::insert_campaign(pool, 1, 1_000, DEADLINE, "Short", "").await;
+    common::insert_event(pool, "donate-1", 101, "donated", 1, "donor", Some(400)).await;
+    common::insert_campaign(pool, 2, 1_000, DEADLINE, "Funded", "").await;
+    common::insert_event(pool, "donate-2", 102, "donated", 2, "donor", Some(1_500)).await;
+}
+
+async fn statuses(pool: &PgPool) -> Vec<(i64, String)> {
+    sqlx::query_as("SELECT campaign_id, status FROM campaign_statuses ORDER BY campaign_id")
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn campaigns_settle_once_their_deadline_passes(pool: PgPool) {
+    seed(&pool).await;
+
+    assert_eq!(
+        sweep_campaign_statuses(&pool, DEADLINE - 60).await.unwrap(),
+        2
+    );
+    assert_eq!(
+        statuses(&pool).await,
+        vec![(1, "active".to_string()), (2, "active".to_string())]
+    );
+
+    // Nothing changed, nothing rewritten
+    assert_eq!(
+        sweep_campaign_statuses(&pool, DEADLINE - 1).await.unwrap(),
+        0
+    );
+
+    assert_eq!(
+        sweep_campaign_statuses(&pool, DEADLINE + 1).await.unwrap(),
+        2
+    );
+    assert_eq!(
+        statuses(&pool).await,
+        vec![(1, "failed".to_string()), (2, "succeeded".to_string())]
+    );
+}
+
+#[sqlx::test]
+async fn swept_status_is_served_by_list_and_detail(pool: PgPool) {
+    seed(&pool).await;
+    common::insert_campaign(&pool, 3, 1_000, DEADLINE, "Not swept yet", "").await;
+    sweep_campaign_statuses(&pool, DEADLINE + 1).await.unwrap();
+    sqlx::query("DELETE FROM campaign_statuses WHERE campaign_id = 3")
+        .execute(&pool)
+        .await
+        .unwrap();
+
+    let (status, body) = common::get_json(common::app_state(pool.clone()), "/v1/campaigns").await;
+    assert_eq!(status, StatusCode::OK);
+    let listed: Vec<&str> = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|c| c["status"].as_str().unwrap())
+        .collect();
+    assert_eq!(listed, vec!["failed", "succeeded", "active"]);
+
+    let (status, body) = common::get_json(common::app_state(pool), "/v1/campaigns/2").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["status"], "succeeded");
+}
+
+#[sqlx::test]
+async fn only_full_withdrawals_mark_a_campaign_withdrawn(pool: PgPool) {
+    seed(&pool).await;
+    // Campaign 2 pays out part of its 1500; campaign 3 pays out everything
+    common::insert_event(
+        &pool,
+        "withdraw-2",
+        201,
+        "withdrawn",
+        2,
+        "creator",
+        Some(600),
+    )
+    .await;
+    common::insert_campaign(&pool, 3, 1_000, DEADLINE, "Paid out", "").await;
+    common::insert_event(&pool, "donate-3", 103, "donated", 3, "donor", Some(1_200)).await;
+    common::insert_event(
+        &pool,
+        "withdraw-3",
+        203,
+        "withdrawn",
+        3,
+        "creator",
+        Some(1_200),
+    )
+    .await;
+
+    sweep_campaign_statuses(&pool, DEADLINE + 1).await.unwrap();
+
+    assert_eq!(
+        statuses(&pool).await,
+        vec![
+            (1, "failed".to_string()),
+            (2, "succeeded".to_string()),
+            (3, "withdrawn".to_string())
+        ]
+    );
+
+    // The snapshot settles it once the account says it is withdrawn
+    sqlx::query(
+        "INSERT INTO campaigns (pubkey, campaign_id, creator_pubkey, goal_amount, amount_raised,
+                                deadline, metadata_url, is_withdrawn, withdrawn_amount,
+                                goal_reached_at, decimals)
+         VALUES ('campaign-pda-2', 2, 'creator', 1000, 1500, $1, '', true, 1500, 0, 9)",
+    )
+    .bind(DEADLINE)
+    .execute(&pool)
+    .await
+    .unwrap();
+    sweep_campaign_statuses(&pool, DEADLINE + 2).await.unwrap();
+
+    assert_eq!(statuses(&pool).await[1], (2, "withdrawn".to_string()));
+}
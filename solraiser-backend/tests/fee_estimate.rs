@@ -0,0 +1,116 @@
+mod common;
+
+use axum::http::StatusCode;
+use base64::Engine;
+use solana_client::{rpc_client::Mocks, rpc_request::RpcRequest};
+use solana_sdk::pubkey::Pubkey;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn breaks_down_rent_signature_and_priority_fees(pool: PgPool) {
+    let mocks = Mocks::from([
+        (
+            RpcRequest::GetMinimumBalanceForRentExemption,
+            serde_json::json!(3_500_000),
+        ),
+        (
+            RpcRequest::GetRecentPrioritizationFees,
+            serde_json::json!([
+                { "slot": 100, "prioritizationFee": 1_000 },
+                { "slot": 101, "prioritizationFee": 7 },
+                { "slot": 102, "prioritizationFee": 250 },
+            ]),
+        ),
+    ]);
+    let state = common::mock_app_state(pool, mocks);
+    state.creator_stats_rent.set(1_000_000).unwrap();
+
+    let (status, body) = common::get_json(state.clone(), "/v1/campaigns/fee-estimate").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["rent_lamports"], 3_500_000);
+    // Without a creator, the estimate is for a first campaign
+    assert_eq!(body["creator_stats_rent_lamports"], 1_000_000);
+    assert_eq!(body["transaction_fee_lamports"], 5_000);
+    // Median of 7, 250 and 1 000 micro-lamports over 200k compute units
+    assert_eq!(body["priority_fee_micro_lamports"], 250);
+    assert_eq!(body["compute_units"], 200_000);
+    assert_eq!(body["priority_fee_lamports"], 50);
+    assert_eq!(body["total_lamports"], 4_505_050);
+    assert_eq!(body["total_sol"], 0.00450505);
+
+    // The rent figure is kept for later requests
+    assert_eq!(state.campaign_rent.get(), Some(&3_500_000));
+}
+
+#[sqlx::test]
+async fn first_campaign_pays_creator_stats_rent(pool: PgPool) {
+    // No account mocked, so the creator has no stats account yet
+    let mocks = Mocks::from([(
+        RpcRequest::GetMinimumBalanceForRentExemption,
+        serde_json::json!(1_000_000),
+    )]);
+    let state = common::mock_app_state(pool, mocks);
+    state.campaign_rent.set(3_500_000).unwrap();
+
+    let (status, body) = common::get_json(
+        state.clone(),
+        &format!(
+            "/v1/campaigns/fee-estimate?creator={}",
+            Pubkey::new_unique()
+        ),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["rent_lamports"], 3_500_000);
+    assert_eq!(body["creator_stats_rent_lamports"], 1_000_000);
+    assert_eq!(state.creator_stats_rent.get(), Some(&1_000_000));
+}
+
+#[sqlx::test]
+async fn existing_creator_stats_cost_nothing(pool: PgPool) {
+    let mocks = Mocks::from([(
+        RpcRequest::GetAccountInfo,
+        serde_json::json!({
+            "context": { "slot": 1 },
+            "value": {
+                "lamports": 1_000_000,
+                "data": [base64::engine::general_purpose::STANDARD.encode([0u8; 13]), "base64"],
+                "owner": Pubkey::new_unique().to_string(),
+                "executable": false,
+                "rentEpoch": 0,
+                "space": 13,
+            },
+        }),
+    )]);
+    let state = common::mock_app_state(pool, mocks);
+    state.campaign_rent.set(3_500_000).unwrap();
+
+    let (status, body) = common::get_json(
+        state,
+        &format!(
+            "/v1/campaigns/fee-estimate?creator={}",
+            Pubkey::new_unique()
+        ),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["creator_stats_rent_lamports"], 0);
+    assert_eq!(
+        body["total_lamports"],
+        3_500_000 + 5_000 + body["priority_fee_lamports"].as_u64().unwrap()
+    );
+}
+
+#[sqlx::test]
+async fn rejects_invalid_creator(pool: PgPool) {
+    let (status, _) = common::get_json(
+        common::app_state(pool),
+        "/v1/campaigns/fee-estimate?creator=not-a-key",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
@@ -0,0 +1,150 @@
+mod common;
+
+use std::sync::Arc;
+
+use axum::http::StatusCode;
+use base64::Engine;
+use solana_client::{rpc_client::Mocks, rpc_request::RpcRequest};
+use solana_sdk::{hash::Hash, pubkey::Pubkey};
+use solraiser_backend::{accounts, idempotency::IdempotencyCache, state::AppState};
+use sqlx::PgPool;
+
+/// Comfortably above the program's minimum goal
+const GOAL: u64 = 1_000_000_000;
+
+fn request(creator: &Pubkey, target_amount: u64) -> serde_json::Value {
+    serde_json::json!({
+        "target_amount": target_amount,
+        "duration": 86_400,
+        "creator": creator.to_string(),
+        "name": "Community library",
+        "description": "Books for the community library",
+        "image_url": "https://example.com/library.png",
+    })
+}
+
+/// State whose RPC answers a `build-create-tx` request once
+fn state_with_registry(pool: PgPool) -> AppState {
+    let mut registry = accounts::account_discriminator("Registry").to_vec();
+    registry.extend(3u64.to_le_bytes());
+    registry.push(255);
+    registry.extend(Pubkey::new_unique().to_bytes());
+    registry.extend(0u32.to_le_bytes());
+
+    let mocks = Mocks::from([
+        (
+            RpcRequest::GetLatestBlockhash,
+            serde_json::json!({
+                "context": { "slot": 1 },
+                "value": { "blockhash": Hash::new_unique().to_string(), "lastValidBlockHeight": 150 },
+            }),
+        ),
+        (
+            RpcRequest::GetAccountInfo,
+            serde_json::json!({
+                "context": { "slot": 1 },
+                "value": {
+                    "lamports": 1_000_000,
+                    "data": [base64::engine::general_purpose::STANDARD.encode(&registry), "base64"],
+                    "owner": Pubkey::new_unique().to_string(),
+                    "executable": false,
+                    "rentEpoch": 0,
+                    "space": registry.len(),
+                },
+            }),
+        ),
+    ]);
+    common::mock_app_state(pool, mocks)
+}
+
+#[sqlx::test]
+async fn repeated_key_returns_the_cached_response(pool: PgPool) {
+    let state = state_with_registry(pool.clone());
+    let creator = Pubkey::new_unique();
+    let headers = [("idempotency-key", "create-library-1")];
+
+    let (status, first) = common::post_json_with_headers(
+        state.clone(),
+        "/v1/campaigns/build-create-tx",
+        request(&creator, GOAL),
+        &headers,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    // Re-running the handler would need an RPC node; the replay doesn't
+    let offline = AppState {
+        rpc_client: common::app_state(pool).rpc_client,
+        ..state
+    };
+    let (status, second) = common::post_json_with_headers(
+        offline,
+        "/v1/campaigns/build-create-tx",
+        request(&creator, GOAL),
+        &headers,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(second, first);
+}
+
+#[sqlx::test]
+async fn key_reused_with_a_different_body_conflicts(pool: PgPool) {
+    let state = state_with_registry(pool);
+    let creator = Pubkey::new_unique();
+    let headers = [("idempotency-key", "create-library-2")];
+
+    let (status, _) = common::post_json_with_headers(
+        state.clone(),
+        "/v1/campaigns/build-create-tx",
+        request(&creator, GOAL),
+        &headers,
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = common::post_json_with_headers(
+        state,
+        "/v1/campaigns/build-create-tx",
+        request(&creator, 2 * GOAL),
+        &headers,
+    )
+    .await;
+    assert_eq!(status, StatusCode::CONFLICT);
+    assert!(body["error"].as_str().unwrap().contains("create-library-2"));
+}
+
+#[sqlx::test]
+async fn full_cache_evicts_the_oldest_response(pool: PgPool) {
+    let cache = Arc::new(IdempotencyCache::new(1));
+    let creator = Pubkey::new_unique();
+
+    for key in ["create-library-3", "create-library-4"] {
+        let state = AppState {
+            idempotency: cache.clone(),
+            ..state_with_registry(pool.clone())
+        };
+        let (status, _) = common::post_json_with_headers(
+            state,
+            "/v1/campaigns/build-create-tx",
+            request(&creator, GOAL),
+            &[("idempotency-key", key)],
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    // The first key was evicted, so the handler runs again and needs an RPC node
+    let offline = AppState {
+        idempotency: cache,
+        ..common::app_state(pool)
+    };
+    let (status, _) = common::post_json_with_headers(
+        offline,
+        "/v1/campaigns/build-create-tx",
+        request(&creator, GOAL),
+        &[("idempotency-key", "create-library-3")],
+    )
+    .await;
+    assert_ne!(status, StatusCode::OK);
+}
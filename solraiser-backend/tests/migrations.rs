@@ -0,0 +1,36 @@
+use solraiser_backend::db;
+use sqlx::PgPool;
+
+#[sqlx::test(migrations = false)]
+async fn migrations_create_expected_tables(pool: PgPool) -> sqlx::Result<()> {
+    db::run_migrations(&pool).await.unwrap();
+    // A second boot against an up-to-date schema must be a no-op
+    db::run_migrations(&pool).await.unwrap();
+
+    let tables: Vec<String> = sqlx::query_scalar(
+        "SELECT table_name::text FROM information_schema.tables WHERE table_schema = 'public'",
+    )
+    .fetch_all(&pool)
+    .await?;
+
+    for expected in [
+        "blocks",
+        "transactions",
+        "account_updates",
+        "campaign_events",
+    ] {
+        assert!(
+            tables.iter().any(|t| t == expected),
+            "missing table {expected}, found {tables:?}"
+        );
+    }
+
+    let dedup_index: Option<String> = sqlx::query_scalar(
+        "SELECT indexname::text FROM pg_indexes WHERE indexname = 'idx_campaign_events_dedup'",
+    )
+    .fetch_optional(&pool)
+    .await?;
+    assert!(dedup_index.is_some());
+
+    Ok(())
+}
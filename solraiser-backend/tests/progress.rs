@@ -0,0 +1,62 @@
+mod common;
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+
+const DAY: i64 = 86_400;
+
+fn cumulative(body: &serde_json::Value) -> Vec<i64> {
+    body.as_array()
+        .unwrap()
+        .iter()
+        .map(|p| p["cumulative_amount"].as_i64().unwrap())
+        .collect()
+}
+
+#[sqlx::test]
+async fn progress_accumulates_donations_across_days(pool: PgPool) {
+    // Created at slot 1; block times are 1_700_000_000 + slot, so each DAY of
+    // slots is one day of block time. The deadline ends the series on day 3.
+    common::insert_campaign(&pool, 1, 1_000, 1_700_000_000 + 3 * DAY, "Garden", "Tools").await;
+    common::insert_event(&pool, "donate-a", 10, "donated", 1, "donor", Some(100)).await;
+    common::insert_event(&pool, "donate-b", 20, "donated", 1, "donor", Some(50)).await;
+    common::insert_event(
+        &pool,
+        "donate-c",
+        10 + DAY,
+        "donated",
+        1,
+        "donor",
+        Some(200),
+    )
+    .await;
+    common::insert_event(&pool, "other", 30, "donated", 2, "donor", Some(999)).await;
+
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        "/v1/campaigns/1/progress?interval=day",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cumulative(&body), vec![150, 350, 350, 350]);
+    assert_eq!(body[0]["t"], "2023-11-14T00:00:00Z");
+}
+
+#[sqlx::test]
+async fn progress_without_donations_is_flat_zero(pool: PgPool) {
+    common::insert_campaign(&pool, 1, 1_000, 1_700_000_000 + DAY, "Garden", "Tools").await;
+
+    let (status, body) =
+        common::get_json(common::app_state(pool), "/v1/campaigns/1/progress").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(cumulative(&body), vec![0, 0]);
+}
+
+#[sqlx::test]
+async fn progress_of_unknown_campaign_is_not_found(pool: PgPool) {
+    let (status, _) = common::get_json(common::app_state(pool), "/v1/campaigns/7/progress").await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
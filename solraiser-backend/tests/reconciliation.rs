@@ -0,0 +1,59 @@
+mod common;
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+
+async fn insert_snapshot(pool: &PgPool, campaign_id: i64, amount_raised: i64) {
+    sqlx::query(
+        "INSERT INTO campaigns (pubkey, campaign_id, creator_pubkey, goal_amount, amount_raised,
+                                deadline, metadata_url, is_withdrawn, withdrawn_amount,
+                                goal_reached_at, decimals)
+         VALUES ($1, $2, 'creator', 1000, $3, 1900000000, '', false, 0, 0, 9)",
+    )
+    .bind(format!("campaign-pda-{campaign_id}"))
+    .bind(campaign_id)
+    .bind(amount_raised)
+    .execute(pool)
+    .await
+    .unwrap();
+}
+
+async fn summary(pool: PgPool, name: &str) -> serde_json::Value {
+    let (status, body) =
+        common::get_json(common::app_state(pool), &format!("/v1/search?q={name}")).await;
+    assert_eq!(status, StatusCode::OK);
+    body[0].clone()
+}
+
+#[sqlx::test]
+async fn missed_donation_is_reported_as_discrepancy(pool: PgPool) {
+    common::insert_campaign(&pool, 1, 1_000, 1_900_000_000, "Library", "").await;
+    common::insert_event(&pool, "donate-1", 10, "donated", 1, "donor", Some(400)).await;
+    // The chain saw a second donation of 250 whose event never got indexed
+    insert_snapshot(&pool, 1, 650).await;
+
+    let campaign = summary(pool, "library").await;
+    assert_eq!(campaign["amount_raised"], 400);
+    assert_eq!(campaign["reconciled"], false);
+    assert_eq!(campaign["discrepancy_lamports"], 250);
+}
+
+#[sqlx::test]
+async fn matching_snapshot_is_reconciled(pool: PgPool) {
+    common::insert_campaign(&pool, 1, 1_000, 1_900_000_000, "Library", "").await;
+    common::insert_event(&pool, "donate-1", 10, "donated", 1, "donor", Some(400)).await;
+    insert_snapshot(&pool, 1, 400).await;
+
+    let campaign = summary(pool, "library").await;
+    assert_eq!(campaign["reconciled"], true);
+    assert_eq!(campaign["discrepancy_lamports"], 0);
+}
+
+#[sqlx::test]
+async fn unsnapshotted_campaign_is_not_yet_reconciled(pool: PgPool) {
+    common::insert_campaign(&pool, 1, 1_000, 1_900_000_000, "Library", "").await;
+
+    let campaign = summary(pool, "library").await;
+    assert!(campaign["reconciled"].is_null());
+    assert!(campaign["discrepancy_lamports"].is_null());
+}
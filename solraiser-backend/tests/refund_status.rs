@@ -0,0 +1,64 @@
+mod common;
+
+use axum::http::StatusCode;
+use solana_sdk::pubkey::Pubkey;
+use solraiser_backend::models::RefundStatus;
+use sqlx::PgPool;
+
+/// Campaign 1 missed its 1 000 goal; campaign 2 is still running
+async fn seed(pool: &PgPool, donor: &str) {
+    common::insert_campaign(pool, 1, 1_000, 1_600_000_000, "Failed", "Missed its goal").await;
+    common::insert_event(pool, "donate-1", 10, "donated", 1, donor, Some(300)).await;
+    common::insert_event(pool, "donate-2", 11, "donated", 1, donor, Some(200)).await;
+
+    common::insert_campaign(pool, 2, 1_000, 4_000_000_000, "Running", "Still open").await;
+    common::insert_event(pool, "donate-3", 12, "donated", 2, donor, Some(100)).await;
+}
+
+#[sqlx::test]
+async fn donor_of_a_failed_campaign_is_told_refunds_are_unsupported(pool: PgPool) {
+    let donor = Pubkey::new_unique().to_string();
+    seed(&pool, &donor).await;
+
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        &format!("/v1/campaigns/1/refund-status?donor={donor}"),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["campaign_failed"], true);
+    assert_eq!(body["contributed"], 500);
+    assert_eq!(body["eligible"], false);
+    assert_eq!(body["reason"], RefundStatus::REFUNDS_UNSUPPORTED);
+}
+
+#[sqlx::test]
+async fn active_campaign_is_not_refundable(pool: PgPool) {
+    let donor = Pubkey::new_unique().to_string();
+    seed(&pool, &donor).await;
+
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        &format!("/v1/campaigns/2/refund-status?donor={donor}"),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["campaign_failed"], false);
+    assert_eq!(body["contributed"], 100);
+    assert_eq!(body["eligible"], false);
+    assert_eq!(body["reason"], "Campaign is still active");
+}
+
+#[sqlx::test]
+async fn rejects_invalid_donor(pool: PgPool) {
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        "/v1/campaigns/1/refund-status?donor=not-a-pubkey",
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(body["error"].as_str().unwrap().contains("not-a-pubkey"));
+}
@@ -0,0 +1,100 @@
+mod common;
+
+use std::sync::{Arc, RwLock};
+
+use solana_client::{
+    rpc_client::{Mocks, RpcClient},
+    rpc_request::RpcRequest,
+};
+use solana_sdk::signature::Signature;
+use solraiser_backend::{
+    reorg::{reconcile_recent_events, ORPHAN_AFTER_MISSES},
+    state::AppState,
+};
+use sqlx::PgPool;
+
+/// State at slot 1000 whose RPC answers one `getSignatureStatuses` call for
+/// `requested`, with no status for the signatures in `missing`
+fn state_with_statuses(pool: PgPool, requested: &[String], missing: &[&String]) -> AppState {
+    // Statuses come back in the (sorted) order they were requested
+    let mut requested = requested.to_vec();
+    requested.sort();
+    let found = serde_json::json!({
+        "slot": 990,
+        "confirmations": null,
+        "status": { "Ok": null },
+        "err": null,
+        "confirmationStatus": "finalized",
+    });
+    let statuses: Vec<_> = requested
+        .iter()
+        .map(|s| {
+            if missing.contains(&s) {
+                serde_json::Value::Null
+            } else {
+                found.clone()
+            }
+        })
+        .collect();
+    let mocks = Mocks::from([(
+        RpcRequest::GetSignatureStatuses,
+        serde_json::json!({ "context": { "slot": 1000 }, "value": statuses }),
+    )]);
+
+    AppState {
+        rpc_client: Arc::new(RpcClient::new_mock_with_mocks("succeeds", mocks)),
+        last_indexed_slot: Arc::new(RwLock::new(1000)),
+        ..common::app_state(pool)
+    }
+}
+
+async fn orphaned(pool: &PgPool) -> Vec<String> {
+    sqlx::query_scalar("SELECT signature FROM campaign_events WHERE orphaned ORDER BY signature")
+        .fetch_all(pool)
+        .await
+        .unwrap()
+}
+
+#[sqlx::test]
+async fn repeatedly_missing_signature_events_are_flagged_orphaned(pool: PgPool) {
+    let kept = Signature::new_unique().to_string();
+    let dropped = Signature::new_unique().to_string();
+    let stale = Signature::new_unique().to_string();
+    common::insert_event(&pool, &kept, 990, "donated", 1, "donor", Some(100)).await;
+    common::insert_event(&pool, &dropped, 995, "donated", 1, "donor", Some(100)).await;
+    // Outside the window: never looked up, so never flagged
+    common::insert_event(&pool, &stale, 100, "donated", 1, "donor", Some(100)).await;
+
+    let recent = [kept.clone(), dropped.clone()];
+    for _ in 1..ORPHAN_AFTER_MISSES {
+        let state = state_with_statuses(pool.clone(), &recent, &[&dropped]);
+        assert_eq!(reconcile_recent_events(&state, 50).await.unwrap(), 0);
+    }
+    assert!(orphaned(&pool).await.is_empty());
+
+    let state = state_with_statuses(pool.clone(), &recent, &[&dropped]);
+    assert_eq!(reconcile_recent_events(&state, 50).await.unwrap(), 1);
+    assert_eq!(orphaned(&pool).await, vec![dropped]);
+}
+
+#[sqlx::test]
+async fn orphaned_events_are_restored_when_their_signature_returns(pool: PgPool) {
+    let flaky = Signature::new_unique().to_string();
+    common::insert_event(&pool, &flaky, 995, "donated", 1, "donor", Some(100)).await;
+    let recent = [flaky.clone()];
+
+    for _ in 0..ORPHAN_AFTER_MISSES {
+        let state = state_with_statuses(pool.clone(), &recent, &[&flaky]);
+        reconcile_recent_events(&state, 50).await.unwrap();
+    }
+    assert_eq!(orphaned(&pool).await, vec![flaky.clone()]);
+
+    let state = state_with_statuses(pool.clone(), &recent, &[]);
+    assert_eq!(reconcile_recent_events(&state, 50).await.unwrap(), 0);
+    assert!(orphaned(&pool).await.is_empty());
+
+    // The miss count starts over, so a single miss doesn't flag it again
+    let state = state_with_statuses(pool.clone(), &recent, &[&flaky]);
+    assert_eq!(reconcile_recent_events(&state, 50).await.unwrap(), 0);
+    assert!(orphaned(&pool).await.is_empty());
+}
@@ -0,0 +1,147 @@
+mod common;
+
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+use solana_client::rpc_client::RpcClient;
+use solraiser_backend::{rate_limit::RateLimiter, state::AppState};
+use sqlx::PgPool;
+
+type Received = Arc<Mutex<Vec<serde_json::Value>>>;
+
+/// Serves `result` to every JSON-RPC call on a local port, recording the
+/// requests, and returns state whose RPC client points at it
+async fn app_state_with_node(pool: PgPool, result: serde_json::Value) -> (AppState, Received) {
+    let received = Received::default();
+    let node = Router::new()
+        .route(
+            "/",
+            post(
+                |State((received, result)): State<(Received, serde_json::Value)>,
+                 Json(request): Json<serde_json::Value>| async move {
+                    let id = request["id"].clone();
+                    received.lock().unwrap().push(request);
+                    Json(serde_json::json!({ "jsonrpc": "2.0", "id": id, "result": result }))
+                },
+            ),
+        )
+        .with_state((received.clone(), result));
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    tokio::spawn(async move { axum::serve(listener, node).await.unwrap() });
+
+    let state = AppState {
+        rpc_client: Arc::new(RpcClient::new(url)),
+        ..common::app_state(pool)
+    };
+    (state, received)
+}
+
+#[sqlx::test]
+async fn allowed_method_is_forwarded(pool: PgPool) {
+    let (state, received) = app_state_with_node(
+        pool,
+        serde_json::json!({ "context": { "slot": 1 }, "value": 42 }),
+    )
+    .await;
+
+    let (status, body) = common::post_json(
+        state,
+        "/v1/rpc",
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "getBalance",
+            "params": ["11111111111111111111111111111111"],
+            "extra": "dropped",
+        }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["id"], 7);
+    assert_eq!(body["result"]["value"], 42);
+    assert_eq!(
+        *received.lock().unwrap(),
+        vec![serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 7,
+            "method": "getBalance",
+            "params": ["11111111111111111111111111111111"],
+        })]
+    );
+}
+
+#[sqlx::test]
+async fn disallowed_method_is_forbidden(pool: PgPool) {
+    let (state, received) = app_state_with_node(pool, serde_json::Value::Null).await;
+
+    let (status, body) = common::post_json(
+        state,
+        "/v1/rpc",
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "sendTransaction",
+            "params": ["AQ=="],
+        }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::FORBIDDEN);
+    assert!(body["error"].as_str().unwrap().contains("sendTransaction"));
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn rejects_extra_params(pool: PgPool) {
+    let (state, received) = app_state_with_node(pool, serde_json::Value::Null).await;
+
+    let (status, _) = common::post_json(
+        state,
+        "/v1/rpc",
+        serde_json::json!({ "method": "getSlot", "params": [{}, {}, {}] }),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+    assert!(received.lock().unwrap().is_empty());
+}
+
+#[sqlx::test]
+async fn requests_over_the_limit_are_throttled(pool: PgPool) {
+    let (state, received) = app_state_with_node(pool, serde_json::json!(100)).await;
+    let state = AppState {
+        rpc_rate_limiter: Arc::new(RateLimiter::new(1, Duration::from_secs(60))),
+        ..state
+    };
+    let request = serde_json::json!({ "id": 1, "method": "getSlot" });
+
+    let (status, _) = common::post_json(state.clone(), "/v1/rpc", request.clone()).await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, body) = common::post_json(state, "/v1/rpc", request).await;
+    assert_eq!(status, StatusCode::TOO_MANY_REQUESTS);
+    assert!(body["error"].as_str().unwrap().contains("Rate limit"));
+    assert_eq!(received.lock().unwrap().len(), 1);
+}
+
+#[sqlx::test]
+async fn transaction_is_fetched_from_the_configured_node(pool: PgPool) {
+    let (state, received) =
+        app_state_with_node(pool, serde_json::json!({ "slot": 9, "meta": null })).await;
+
+    let (status, body) = common::get_json(state, "/v1/transaction/5sig").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["signature"], "5sig");
+    assert_eq!(body["data"]["slot"], 9);
+    let received = received.lock().unwrap();
+    assert_eq!(received.len(), 1);
+    assert_eq!(received[0]["method"], "getTransaction");
+    assert_eq!(received[0]["params"][0], "5sig");
+}
@@ -0,0 +1,56 @@
+mod common;
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+
+async fn seed(pool: &PgPool) {
+    common::insert_campaign(
+        pool,
+        1,
+        1_000,
+        1_900_000_000,
+        "Solar panels for the school",
+        "Help us install solar panels on the village school roof",
+    )
+    .await;
+    common::insert_campaign(
+        pool,
+        2,
+        5_000,
+        1_900_000_000,
+        "Community garden",
+        "Raised beds and tools for the neighbourhood garden",
+    )
+    .await;
+    common::insert_event(pool, "donate-1", 100, "donated", 1, "donor", Some(400)).await;
+}
+
+#[sqlx::test]
+async fn search_matches_keyword_with_summary(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) = common::get_json(common::app_state(pool), "/v1/search?q=solar").await;
+
+    assert_eq!(status, StatusCode::OK);
+    let results = body.as_array().unwrap();
+    assert_eq!(results.len(), 1);
+    assert_eq!(results[0]["campaign_id"], 1);
+    assert_eq!(results[0]["name"], "Solar panels for the school");
+    assert_eq!(results[0]["amount_raised"], 400);
+}
+
+#[sqlx::test]
+async fn search_without_matches_returns_empty_array(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) = common::get_json(common::app_state(pool), "/v1/search?q=spaceship").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body, serde_json::json!([]));
+}
+
+#[sqlx::test]
+async fn search_rejects_empty_query(pool: PgPool) {
+    let (status, _) = common::get_json(common::app_state(pool), "/v1/search?q=%20").await;
+    assert_eq!(status, StatusCode::BAD_REQUEST);
+}
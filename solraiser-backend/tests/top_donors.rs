@@ -0,0 +1,61 @@
+mod common;
+
+use axum::http::StatusCode;
+use solraiser_backend::indexer::ANONYMOUS_DONOR;
+use sqlx::PgPool;
+
+async fn seed(pool: &PgPool) {
+    common::insert_campaign(pool, 1, 10_000, 4_000_000_000, "Library", "Books").await;
+    common::insert_event(pool, "donate-1", 10, "donated", 1, "alice", Some(300)).await;
+    common::insert_event(pool, "donate-2", 11, "donated", 1, "bob", Some(500)).await;
+    common::insert_event(pool, "donate-3", 12, "donated", 1, "alice", Some(400)).await;
+    common::insert_event(
+        pool,
+        "donate-4",
+        13,
+        "donated",
+        1,
+        ANONYMOUS_DONOR,
+        Some(900),
+    )
+    .await;
+}
+
+#[sqlx::test]
+async fn donors_are_ranked_by_total(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) =
+        common::get_json(common::app_state(pool), "/v1/campaigns/1/top-donors").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        serde_json::json!([
+            { "donor": "alice", "total_amount": 700, "donation_count": 2 },
+            { "donor": "bob", "total_amount": 500, "donation_count": 1 },
+        ])
+    );
+}
+
+#[sqlx::test]
+async fn anonymous_donation_counts_toward_total_but_is_not_listed(pool: PgPool) {
+    seed(&pool).await;
+
+    let (status, body) = common::get_json(common::app_state(pool.clone()), "/v1/campaigns/1").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["amount_raised"], 2_100);
+    assert_eq!(body["donation_count"], 4);
+
+    let (status, body) = common::get_json(
+        common::app_state(pool),
+        "/v1/campaigns/1/top-donors?limit=5",
+    )
+    .await;
+    assert_eq!(status, StatusCode::OK);
+    assert!(body
+        .as_array()
+        .unwrap()
+        .iter()
+        .all(|donor| donor["donor"] != ANONYMOUS_DONOR));
+}
@@ -0,0 +1,108 @@
+mod common;
+
+use axum::http::StatusCode;
+use base64::Engine;
+use solana_client::{rpc_client::Mocks, rpc_request::RpcRequest};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use solraiser_backend::{
+    indexer::{event_discriminator, CampaignDonated},
+    state::AppState,
+};
+use sqlx::PgPool;
+
+/// `getTransaction` response for a confirmed donation to `program_id`, in the
+/// RPC's JSON shape
+fn donation_transaction(
+    program_id: &Pubkey,
+    signature: &Signature,
+    donated: &CampaignDonated,
+) -> serde_json::Value {
+    let mut data = event_discriminator("CampaignDonated").to_vec();
+    data.extend(borsh::to_vec(donated).unwrap());
+
+    serde_json::json!({
+        "slot": 4242,
+        "blockTime": 1_700_000_000,
+        "transaction": {
+            "signatures": [signature.to_string()],
+            "message": {
+                "header": {
+                    "numRequiredSignatures": 1,
+                    "numReadonlySignedAccounts": 0,
+                    "numReadonlyUnsignedAccounts": 1,
+                },
+                "accountKeys": [donated.donor_pubkey.to_string()],
+                "recentBlockhash": "11111111111111111111111111111111",
+                "instructions": [],
+            },
+        },
+        "meta": {
+            "err": null,
+            "status": { "Ok": null },
+            "fee": 5000,
+            "preBalances": [],
+            "postBalances": [],
+            "logMessages": [
+                format!("Program {program_id} invoke [1]"),
+                "Program log: Instruction: Donate",
+                format!(
+                    "Program data: {}",
+                    base64::engine::general_purpose::STANDARD.encode(data)
+                ),
+                format!("Program {program_id} success"),
+            ],
+        },
+    })
+}
+
+#[sqlx::test]
+async fn decodes_donation_event_from_rpc_transaction(pool: PgPool) {
+    let signature = Signature::new_unique();
+    let donated = CampaignDonated {
+        campaign_id: 7,
+        donor_pubkey: Pubkey::new_unique(),
+        amount: 250_000_000,
+        goal_reached_at: 0,
+        amount_raised: 750_000_000,
+        goal_amount: 1_000_000_000,
+    };
+    let program_id = Pubkey::new_unique();
+    let mocks = Mocks::from([(
+        RpcRequest::GetTransaction,
+        donation_transaction(&program_id, &signature, &donated),
+    )]);
+    let state = AppState {
+        program_id,
+        ..common::mock_app_state(pool, mocks)
+    };
+
+    let (status, body) =
+        common::get_json(state, &format!("/v1/transaction/{signature}/events")).await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(
+        body,
+        serde_json::json!([{
+            "event_type": "donated",
+            "campaign_id": 7,
+            "donor_pubkey": donated.donor_pubkey.to_string(),
+            "amount": 250_000_000,
+            "goal_reached_at": 0,
+            "amount_raised": 750_000_000,
+            "goal_amount": 1_000_000_000,
+        }])
+    );
+}
+
+#[sqlx::test]
+async fn unknown_signature_is_not_found(pool: PgPool) {
+    let mocks = Mocks::from([(RpcRequest::GetTransaction, serde_json::Value::Null)]);
+
+    let (status, _) = common::get_json(
+        common::mock_app_state(pool, mocks),
+        &format!("/v1/transaction/{}/events", Signature::new_unique()),
+    )
+    .await;
+
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
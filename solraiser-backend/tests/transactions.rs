@@ -0,0 +1,35 @@
+mod common;
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn failed_transaction_error_detail_round_trips(pool: PgPool) {
+    common::insert_block(&pool, 42, Some(1_700_000_000)).await;
+
+    let error_detail = serde_json::json!({
+        "err": { "InstructionError": [0, { "Custom": 6008 }] },
+        "logs": ["Program log: AnchorError occurred. Error Code: GoalNotReached."],
+    });
+    sqlx::query(
+        "INSERT INTO transactions (signature, slot, block_time, success, fee, error_detail)
+         VALUES ('failed-sig', 42, 1700000000, false, 5000, $1)",
+    )
+    .bind(&error_detail)
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let (status, body) =
+        common::get_json(common::app_state(pool), "/v1/transactions/failed-sig").await;
+
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body[0]["success"], false);
+    assert_eq!(body[0]["error_detail"], error_detail);
+}
+
+#[sqlx::test]
+async fn unknown_transaction_is_not_found(pool: PgPool) {
+    let (status, _) = common::get_json(common::app_state(pool), "/v1/transactions/missing").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
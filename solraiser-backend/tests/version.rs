@@ -0,0 +1,25 @@
+mod common;
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+
+#[sqlx::test]
+async fn version_reports_build_and_program(pool: PgPool) {
+    let state = common::app_state(pool);
+    let program_id = state.program_id;
+
+    let (status, body) = common::get_json(state, "/version").await;
+    assert_eq!(status, StatusCode::OK);
+    assert_eq!(body["program_id"], program_id.to_string());
+    assert!(!body["version"].as_str().unwrap().is_empty());
+    assert_eq!(body["network"], "localnet");
+}
+
+#[sqlx::test]
+async fn data_routes_are_only_served_under_v1(pool: PgPool) {
+    let (status, _) = common::get_json(common::app_state(pool.clone()), "/v1/events").await;
+    assert_eq!(status, StatusCode::OK);
+
+    let (status, _) = common::get_json(common::app_state(pool), "/events").await;
+    assert_eq!(status, StatusCode::NOT_FOUND);
+}
@@ -22,7 +22,6 @@ export default function CreateCampaignPage() {
   const [isSubmitting, setIsSubmitting] = useState(false);
 
   // Form state
-  const [campaignId, setCampaignId] = useState("");
   const [goalAmount, setGoalAmount] = useState("");
   const [deadline, setDeadline] = useState("");
   const [metadataUrl, setMetadataUrl] = useState("");
@@ -36,17 +35,11 @@ export default function CreateCampaignPage() {
     }
 
     // Validation
-    if (!campaignId || !goalAmount || !deadline || !metadataUrl) {
+    if (!goalAmount || !deadline || !metadataUrl) {
       toast.error("All fields are required");
       return;
     }
 
-    const campaignIdNum = parseInt(campaignId);
-    if (isNaN(campaignIdNum) || campaignIdNum < 0) {
-      toast.error("Campaign ID must be a valid positive number");
-      return;
-    }
-
     const goalAmountNum = parseFloat(goalAmount);
     if (isNaN(goalAmountNum) || goalAmountNum <= 0) {
       toast.error("Goal amount must be greater than 0");
@@ -66,9 +59,8 @@ export default function CreateCampaignPage() {
 
     try {
       // Create the campaign on Solana
-      const txSignature = await createCampaign({
+      const { signature: txSignature, campaignId } = await createCampaign({
         program,
-        campaignId: campaignIdNum,
         goalAmount: goalAmountNum,
         deadline: deadlineTimestamp,
         metadataUrl,
@@ -76,7 +68,7 @@ export default function CreateCampaignPage() {
       });
 
       toast.success("Campaign created successfully!", {
-        description: `Campaign ID: ${campaignId}`,
+        description: `Campaign ID: ${campaignId.toString()}`,
         action: {
           label: "View Transaction",
           onClick: () => {
@@ -91,7 +83,6 @@ export default function CreateCampaignPage() {
       //TODO : Send the transaction signature to backend /transaction/create-campaign
 
       // Reset form
-      setCampaignId("");
       setGoalAmount("");
       setDeadline("");
       setMetadataUrl("");
@@ -149,31 +140,6 @@ export default function CreateCampaignPage() {
 
           <CardContent>
             <form onSubmit={handleSubmit} className="space-y-6">
-              {/* Campaign ID */}
-              <div className="space-y-2">
-                <Label
-                  htmlFor="campaignId"
-                  className="text-slate-300 flex items-center gap-2"
-                >
-                  <Target className="w-4 h-4 text-[rgb(var(--solraiser-violet-400))]" />
-                  Campaign ID
-                </Label>
-                <input
-                  id="campaignId"
-                  type="number"
-                  value={campaignId}
-                  onChange={(e) => setCampaignId(e.target.value)}
-                  placeholder="Enter unique campaign ID (e.g., 1, 2, 3...)"
-                  className="w-full px-4 py-3 bg-[rgb(var(--solraiser-slate-700))]/50 border border-white/10 rounded-xl text-white placeholder:text-slate-500 focus:outline-none focus:ring-2 focus:ring-[rgb(var(--solraiser-violet-500))]/50 focus:border-[rgb(var(--solraiser-violet-400))] transition-all"
-                  required
-                  min="0"
-                  step="1"
-                />
-                <p className="text-xs text-slate-500">
-                  A unique identifier for your campaign
-                </p>
-              </div>
-
               {/* Goal Amount */}
               <div className="space-y-2">
                 <Label
@@ -272,7 +238,7 @@ export default function CreateCampaignPage() {
                 Important Notes
               </h3>
               <ul className="text-xs text-slate-400 space-y-1 list-disc list-inside">
-                <li>Campaign ID must be unique for your wallet</li>
+                <li>Campaign IDs are assigned by the program in creation order</li>
                 <li>Goal amount must be greater than 0</li>
                 <li>Deadline must be set in the future</li>
                 <li>Metadata URL should link to valid JSON containing campaign details</li>
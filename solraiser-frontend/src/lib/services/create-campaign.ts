@@ -3,57 +3,80 @@ import { PublicKey } from "@solana/web3.js";
 
 interface CreateCampaignParams {
   program: Program;
-  campaignId: number;
   goalAmount: number;
   deadline: number;
   metadataUrl: string;
   creatorPublicKey: PublicKey;
 }
 
+interface CreatedCampaign {
+  signature: string;
+  campaignId: BN;
+}
+
+// Campaigns created here raise SOL, displayed with 9 decimals
+const SOL_DECIMALS = 9;
+// The program's fixed-width symbol: "SOL" zero-padded to 8 bytes
+const SOL_SYMBOL = [..."SOL"].map((c) => c.charCodeAt(0)).concat([0, 0, 0, 0, 0]);
+
 /**
  * Creates a new campaign on the Solana blockchain
+ * The program assigns the campaign id from the registry's `nextId`
  * @param params - Campaign creation parameters
- * @returns Transaction signature
+ * @returns Transaction signature and the id the campaign was created with
  */
-export async function createCampaign(params: CreateCampaignParams): Promise<string> {
-  const { program, campaignId, goalAmount, deadline, metadataUrl, creatorPublicKey } = params;
+export async function createCampaign(params: CreateCampaignParams): Promise<CreatedCampaign> {
+  const { program, goalAmount, deadline, metadataUrl, creatorPublicKey } = params;
 
   try {
     // Convert numbers to BN for Anchor
-    const campaignIdBN = new BN(campaignId);
     const goalLamports = new BN(Math.floor(goalAmount * 1e9)); // Convert SOL to lamports
     const deadlineBN = new BN(deadline);
 
-    // Derive the campaign PDA
+    const [registryPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("registry")],
+      program.programId
+    );
+    const [creatorStatsPda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("creator_stats"), creatorPublicKey.toBuffer()],
+      program.programId
+    );
+
+    // Derive the campaign PDA from the id the registry will assign next
+    const registry = await program.account.registry.fetch(registryPda);
+    const campaignId = registry.nextId as BN;
     const [campaignPda, _campaignBump] = PublicKey.findProgramAddressSync(
       [
         Buffer.from("campaign"),
         creatorPublicKey.toBuffer(),
-        campaignIdBN.toArrayLike(Buffer, "le", 8),
+        campaignId.toArrayLike(Buffer, "le", 8),
       ],
       program.programId
     );
 
     console.log("Creating campaign with PDA:", campaignPda.toString());
 
-    // Call the program
+    // Call the program; no withdrawal cooldown, payouts go to the creator
     const txSignature = await program.methods
-      .createCampaign(campaignIdBN, goalLamports, deadlineBN, metadataUrl)
-      .accounts({
+      .createCampaign(goalLamports, deadlineBN, metadataUrl, new BN(0), SOL_DECIMALS, SOL_SYMBOL, [])
+      .accountsPartial({
+        registry: registryPda,
         campaignAccount: campaignPda,
+        creatorStats: creatorStatsPda,
+        beneficiaries: null,
         creator: creatorPublicKey,
       })
       .rpc();
 
     console.log("Campaign created successfully. Signature:", txSignature);
-    return txSignature;
+    return { signature: txSignature, campaignId };
   } catch (error) {
     console.error("Error creating campaign:", error);
-    
+
     // Extract meaningful error message
     if (error instanceof Error) {
       throw new Error(`Failed to create campaign: ${error.message}`);
     }
     throw error;
   }
-}
\ No newline at end of file
+}
@@ -2,18 +2,78 @@ use anchor_lang::prelude::*;
 
 declare_id!("62NbBCCxPfR83xtgw3AaxKGHyyDdxobrcCGzA7s7LFie");
 
+/// Maximum campaigns per `donate_batch`. Each entry costs a PDA derivation, a
+/// system transfer CPI and an event, so 10 keeps the worst case well inside the
+/// default 200k compute unit budget and the transaction size limit.
+pub const MAX_BATCH_DONATIONS: usize = 10;
+
+/// Maximum beneficiaries sharing a campaign's payout. Each one is a writable
+/// account on `withdraw_split`, so the list stays short.
+pub const MAX_BENEFICIARIES: usize = 5;
+
+/// Beneficiary shares are in basis points and must add up to exactly this
+pub const TOTAL_SHARE_BPS: u16 = 10_000;
+
+/// Lamports a goal must exceed the campaign's rent-exempt minimum by, so a
+/// reached goal always leaves something withdrawable above rent
+pub const MIN_GOAL_ABOVE_RENT: u64 = 1_000_000;
+
+/// `Campaign::progress_bps` of a campaign at or past its goal
+pub const FULL_PROGRESS_BPS: u16 = 10_000;
+
 #[program]
 pub mod solraiser {
     use super::*;
+    /// Creates the global registry that hands out campaign ids; run once per deployment
+    /// Only the program's upgrade authority may sign, and it becomes the platform admin,
+    /// so nobody can claim the registry between deployment and initialization
+    /// `max_campaigns_per_creator` caps each creator's open campaigns (0 = no cap)
+    pub fn initialize_registry(
+        ctx: Context<InitializeRegistry>,
+        max_campaigns_per_creator: u32,
+    ) -> Result<()> {
+        let registry = &mut ctx.accounts.registry;
+        registry.next_id = 0;
+        registry.bump = ctx.bumps.registry;
+        registry.admin = ctx.accounts.admin.key();
+        registry.max_campaigns_per_creator = max_campaigns_per_creator;
+        Ok(())
+    }
+
+    /// Changes the per-creator campaign cap (0 = no cap); admin only
+    /// Creators already above a lowered cap keep their campaigns but can't open new ones
+    pub fn set_max_campaigns_per_creator(
+        ctx: Context<UpdateRegistry>,
+        max_campaigns_per_creator: u32,
+    ) -> Result<()> {
+        ctx.accounts.registry.max_campaigns_per_creator = max_campaigns_per_creator;
+        Ok(())
+    }
+
     /// Creates a new fundraising campaign
+    /// The campaign id is assigned from the registry's `next_id`, so ids run 0, 1, 2, ...
+    /// across all creators and never collide
+    /// `withdraw_cooldown_secs` is the minimum gap between withdrawals (0 disables it)
+    /// `decimals` and `symbol` only describe how to display amounts; they are not enforced
+    /// `beneficiaries` fixes the payout split for the campaign's whole life, so donors
+    /// always see the split they are funding; empty pays out to the creator, otherwise
+    /// the `beneficiaries` account must be passed and payouts go through `withdraw_split`
+    #[allow(clippy::too_many_arguments)]
     pub fn create_campaign(
         ctx: Context<CreateCampaign>,
-        campaign_id: u64,
         goal_amount: u64,
         deadline: i64,
         metadata_url: String,
+        withdraw_cooldown_secs: u64,
+        decimals: u8,
+        symbol: [u8; 8],
+        beneficiaries: Vec<BeneficiaryShare>,
     ) -> Result<()> {
         require!(goal_amount > 0, ErrorCode::InvalidGoalAmount);
+        require!(
+            goal_amount >= Campaign::min_goal_amount(&Rent::get()?),
+            ErrorCode::GoalBelowMinimum
+        );
         require!(
             deadline > Clock::get()?.unix_timestamp,
             ErrorCode::InvalidDeadline
@@ -22,6 +82,36 @@ pub mod solraiser {
             metadata_url.len() <= Campaign::MAX_METADATA_URL_LEN,
             ErrorCode::MetadataUrlTooLong
         );
+        require!(
+            Campaign::is_valid_metadata_url(&metadata_url),
+            ErrorCode::InvalidMetadataUrl
+        );
+        require!(
+            beneficiaries.is_empty() || Beneficiaries::is_valid_split(&beneficiaries),
+            ErrorCode::InvalidBeneficiaryShares
+        );
+        require!(
+            beneficiaries.is_empty() == ctx.accounts.beneficiaries.is_none(),
+            ErrorCode::BeneficiaryAccountMismatch
+        );
+
+        let registry = &mut ctx.accounts.registry;
+        let stats = &mut ctx.accounts.creator_stats;
+        require!(
+            registry.max_campaigns_per_creator == 0
+                || stats.active_campaign_count < registry.max_campaigns_per_creator,
+            ErrorCode::TooManyCampaigns
+        );
+        stats.active_campaign_count = stats
+            .active_campaign_count
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+        stats.bump = ctx.bumps.creator_stats;
+
+        let campaign_id = registry.next_id;
+        registry.next_id = campaign_id
+            .checked_add(1)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
 
         let campaign = &mut ctx.accounts.campaign_account;
         campaign.creator_pubkey = ctx.accounts.creator.key();
@@ -32,6 +122,18 @@ pub mod solraiser {
         campaign.metadata_url = metadata_url.clone();
         campaign.is_withdrawn = false;
         campaign.withdrawn_amount = 0;
+        campaign.goal_reached_at = 0;
+        campaign.withdraw_cooldown_secs = withdraw_cooldown_secs;
+        campaign.last_withdraw_at = 0;
+        campaign.decimals = decimals;
+        campaign.symbol = symbol;
+        campaign.has_beneficiaries = !beneficiaries.is_empty();
+
+        if let Some(split) = &mut ctx.accounts.beneficiaries {
+            split.campaign = campaign.key();
+            split.shares = beneficiaries;
+            split.bump = ctx.bumps.beneficiaries.unwrap();
+        }
 
         emit!(CampaignCreated {
             campaign_id,
@@ -39,6 +141,8 @@ pub mod solraiser {
             goal_amount,
             deadline,
             metadata_url,
+            decimals,
+            symbol,
         });
         Ok(())
     }
@@ -46,47 +150,178 @@ pub mod solraiser {
     /// Donates funds to an active campaign
     /// Allows overfunding beyond goal (common crowdfunding behavior)
     pub fn donate(ctx: Context<Donate>, amount: u64) -> Result<()> {
-        require!(amount > 0, ErrorCode::InvalidAmount);
+        let donor = ctx.accounts.donor.key();
+        donate_as(ctx, amount, donor)
+    }
 
-        let campaign = &ctx.accounts.campaign_account;
+    /// Like `donate`, but the `CampaignDonated` event carries `Pubkey::default()`
+    /// instead of the donor, keeping the wallet off indexed donor lists
+    /// This is not private: the donor still signs and pays, so the wallet is
+    /// visible in the transaction itself to anyone who looks it up
+    pub fn donate_anonymous(ctx: Context<Donate>, amount: u64) -> Result<()> {
+        donate_as(ctx, amount, Pubkey::default())
+    }
 
+    /// Donates to several campaigns in one transaction
+    /// Campaign PDAs are passed as writable remaining accounts, one per entry in `amounts`;
+    /// any invalid or expired campaign fails the whole batch
+    pub fn donate_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DonateBatch<'info>>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        require!(
+            !amounts.is_empty() && amounts.len() <= MAX_BATCH_DONATIONS,
+            ErrorCode::InvalidBatchSize
+        );
         require!(
-            Clock::get()?.unix_timestamp < campaign.deadline,
-            ErrorCode::CampaignExpired
+            amounts.len() == ctx.remaining_accounts.len(),
+            ErrorCode::BatchLengthMismatch
         );
 
-        let cpi_accounts = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.donor.to_account_info(),
-            to: ctx.accounts.campaign_account.to_account_info(),
-        };
-        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
-        anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+        let now = Clock::get()?.unix_timestamp;
+
+        for (campaign_info, &amount) in ctx.remaining_accounts.iter().zip(amounts.iter()) {
+            require!(amount > 0, ErrorCode::InvalidAmount);
+            require!(campaign_info.is_writable, ErrorCode::InvalidCampaignAccount);
+
+            let mut campaign = Account::<Campaign>::try_from(campaign_info)?;
+            let (expected_pda, _bump) = Pubkey::find_program_address(
+                &[
+                    b"campaign",
+                    campaign.creator_pubkey.as_ref(),
+                    campaign.campaign_id.to_le_bytes().as_ref(),
+                ],
+                ctx.program_id,
+            );
+            require_keys_eq!(
+                campaign_info.key(),
+                expected_pda,
+                ErrorCode::InvalidCampaignAccount
+            );
+            require!(now < campaign.deadline, ErrorCode::CampaignExpired);
+
+            let cpi_accounts = anchor_lang::system_program::Transfer {
+                from: ctx.accounts.donor.to_account_info(),
+                to: campaign_info.clone(),
+            };
+            let cpi_ctx =
+                CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+            anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+
+            campaign.record_donation(amount, now)?;
+            campaign.exit(ctx.program_id)?;
+
+            emit!(CampaignDonated {
+                campaign_id: campaign.campaign_id,
+                donor_pubkey: ctx.accounts.donor.key(),
+                amount,
+                goal_reached_at: campaign.goal_reached_at,
+                amount_raised: campaign.amount_raised,
+                goal_amount: campaign.goal_amount,
+            });
+        }
+        Ok(())
+    }
 
+    /// Withdraws funds from a successful campaign
+    /// Withdraws ALL funds (including overfunding) to prevent locked lamports
+    /// Pays out to `destination` when given, otherwise to the creator
+    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+        let destination = ctx.accounts.payout_account();
         let campaign = &mut ctx.accounts.campaign_account;
-        campaign.amount_raised = campaign
-            .amount_raised
-            .checked_add(amount)
+
+        let now = Clock::get()?.unix_timestamp;
+        require!(now > campaign.deadline, ErrorCode::CampaignStillActive);
+        campaign.check_withdraw_cooldown(now)?;
+        let campaign_lamports = campaign.to_account_info().lamports();
+
+        let rent = Rent::get()?;
+        let min_rent = rent.minimum_balance(Campaign::LEN);
+        let withdraw_amount = campaign_lamports
+            .checked_sub(min_rent)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+
+        require!(withdraw_amount > 0, ErrorCode::InsufficientFunds);
+
+        transfer_from_campaign(&campaign.to_account_info(), &destination, withdraw_amount)?;
+
+        campaign.is_withdrawn = true;
+        campaign.last_withdraw_at = now;
+        campaign.withdrawn_amount = campaign
+            .withdrawn_amount
+            .checked_add(withdraw_amount)
             .ok_or(ErrorCode::ArithmeticOverflow)?;
 
-        emit!(CampaignDonated {
+        emit!(CampaignWithdrawn {
             campaign_id: campaign.campaign_id,
-            donor_pubkey: ctx.accounts.donor.key(),
-            amount,
+            creator_pubkey: ctx.accounts.creator.key(),
+            amount: withdraw_amount,
+            destination: destination.key(),
         });
         Ok(())
     }
 
-    /// Withdraws funds from a successful campaign
-    /// Withdraws ALL funds (including overfunding) to prevent locked lamports
-    pub fn withdraw(ctx: Context<Withdraw>) -> Result<()> {
+    /// Withdraws exactly `amount` from a successful campaign, leaving the rest on-chain
+    /// The campaign is only marked withdrawn once its balance is down to the rent minimum
+    pub fn withdraw_partial(ctx: Context<Withdraw>, amount: u64) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let destination = ctx.accounts.payout_account();
         let campaign = &mut ctx.accounts.campaign_account;
 
+        let now = Clock::get()?.unix_timestamp;
+        require!(now > campaign.deadline, ErrorCode::CampaignStillActive);
+        campaign.check_withdraw_cooldown(now)?;
+        let campaign_lamports = campaign.to_account_info().lamports();
+
+        let rent = Rent::get()?;
+        let min_rent = rent.minimum_balance(Campaign::LEN);
+        let remaining = campaign_lamports
+            .checked_sub(amount)
+            .ok_or(ErrorCode::InsufficientFunds)?;
+
+        require!(remaining >= min_rent, ErrorCode::InsufficientFunds);
+
+        transfer_from_campaign(&campaign.to_account_info(), &destination, amount)?;
+
+        campaign.is_withdrawn = remaining == min_rent;
+        campaign.last_withdraw_at = now;
+        campaign.withdrawn_amount = campaign
+            .withdrawn_amount
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        emit!(CampaignWithdrawn {
+            campaign_id: campaign.campaign_id,
+            creator_pubkey: ctx.accounts.creator.key(),
+            amount,
+            destination: destination.key(),
+        });
+        Ok(())
+    }
+
+    /// Withdraws all funds of a successful campaign, split between its beneficiaries
+    /// Beneficiary accounts are passed as writable remaining accounts, in the order
+    /// they were registered; each gets its share, and the last one also gets the
+    /// rounding remainder so no lamports are left behind above the rent minimum
+    pub fn withdraw_split<'info>(
+        ctx: Context<'_, '_, 'info, 'info, WithdrawSplit<'info>>,
+    ) -> Result<()> {
+        let shares = &ctx.accounts.beneficiaries.shares;
+        require!(
+            Beneficiaries::is_valid_split(shares),
+            ErrorCode::InvalidBeneficiaryShares
+        );
         require!(
-            Clock::get()?.unix_timestamp > campaign.deadline,
-            ErrorCode::CampaignStillActive
+            ctx.remaining_accounts.len() == shares.len(),
+            ErrorCode::BeneficiaryAccountMismatch
         );
+
+        let campaign = &mut ctx.accounts.campaign_account;
+        let now = Clock::get()?.unix_timestamp;
+        require!(now > campaign.deadline, ErrorCode::CampaignStillActive);
+        campaign.check_withdraw_cooldown(now)?;
         let campaign_lamports = campaign.to_account_info().lamports();
-        let creator_lamports = ctx.accounts.creator.to_account_info().lamports();
 
         let rent = Rent::get()?;
         let min_rent = rent.minimum_balance(Campaign::LEN);
@@ -96,40 +331,190 @@ pub mod solraiser {
 
         require!(withdraw_amount > 0, ErrorCode::InsufficientFunds);
 
-        **campaign.to_account_info().try_borrow_mut_lamports()? = min_rent;
+        let mut remaining = withdraw_amount;
+        for (i, (share, beneficiary)) in shares.iter().zip(ctx.remaining_accounts).enumerate() {
+            require_keys_eq!(
+                beneficiary.key(),
+                share.pubkey,
+                ErrorCode::BeneficiaryAccountMismatch
+            );
+            require!(
+                beneficiary.is_writable,
+                ErrorCode::BeneficiaryAccountMismatch
+            );
+
+            let amount = if i + 1 == shares.len() {
+                remaining
+            } else {
+                // share_bps <= 10_000, so this never exceeds withdraw_amount
+                (withdraw_amount as u128 * share.share_bps as u128 / TOTAL_SHARE_BPS as u128) as u64
+            };
+            remaining -= amount;
+
+            transfer_from_campaign(&campaign.to_account_info(), beneficiary, amount)?;
+
+            emit!(CampaignWithdrawn {
+                campaign_id: campaign.campaign_id,
+                creator_pubkey: ctx.accounts.creator.key(),
+                amount,
+                destination: beneficiary.key(),
+            });
+        }
 
-        **ctx
-            .accounts
-            .creator
-            .to_account_info()
-            .try_borrow_mut_lamports()? = creator_lamports
+        campaign.is_withdrawn = true;
+        campaign.last_withdraw_at = now;
+        campaign.withdrawn_amount = campaign
+            .withdrawn_amount
             .checked_add(withdraw_amount)
             .ok_or(ErrorCode::ArithmeticOverflow)?;
+        Ok(())
+    }
 
-        campaign.is_withdrawn = true;
-        campaign.withdrawn_amount = withdraw_amount;
+    /// Closes a campaign, returning its rent (and its beneficiaries' rent) to the
+    /// creator and freeing one of their campaign slots
+    /// Only campaigns that never received donations or have been fully withdrawn
+    /// can be closed, so donated funds are never swept by a close
+    pub fn close_campaign(ctx: Context<CloseCampaign>) -> Result<()> {
+        let campaign = &ctx.accounts.campaign_account;
+        require!(
+            campaign.amount_raised == 0 || campaign.is_withdrawn,
+            ErrorCode::CampaignNotClosable
+        );
+        require!(
+            ctx.accounts.beneficiaries.is_some() == campaign.has_beneficiaries,
+            ErrorCode::BeneficiaryAccountMismatch
+        );
 
-        emit!(CampaignWithdrawn {
+        let stats = &mut ctx.accounts.creator_stats;
+        stats.active_campaign_count = stats.active_campaign_count.saturating_sub(1);
+
+        emit!(CampaignClosed {
             campaign_id: campaign.campaign_id,
             creator_pubkey: ctx.accounts.creator.key(),
-            amount: withdraw_amount,
         });
         Ok(())
     }
 }
 
+/// Transfers `amount` from the donor to the campaign and emits `CampaignDonated`
+/// with `donor_pubkey` as the reported donor
+fn donate_as(ctx: Context<Donate>, amount: u64, donor_pubkey: Pubkey) -> Result<()> {
+    require!(amount > 0, ErrorCode::InvalidAmount);
+
+    let campaign = &ctx.accounts.campaign_account;
+    let now = Clock::get()?.unix_timestamp;
+
+    require!(now < campaign.deadline, ErrorCode::CampaignExpired);
+
+    let cpi_accounts = anchor_lang::system_program::Transfer {
+        from: ctx.accounts.donor.to_account_info(),
+        to: ctx.accounts.campaign_account.to_account_info(),
+    };
+    let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
+    anchor_lang::system_program::transfer(cpi_ctx, amount)?;
+
+    let campaign = &mut ctx.accounts.campaign_account;
+    campaign.record_donation(amount, now)?;
+
+    emit!(CampaignDonated {
+        campaign_id: campaign.campaign_id,
+        donor_pubkey,
+        amount,
+        goal_reached_at: campaign.goal_reached_at,
+        amount_raised: campaign.amount_raised,
+        goal_amount: campaign.goal_amount,
+    });
+    Ok(())
+}
+
+/// Moves lamports out of the campaign PDA. The PDA is owned by this program, so
+/// its balance is debited directly rather than through a system transfer.
+fn transfer_from_campaign(campaign: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    let campaign_lamports = campaign.lamports();
+    let to_lamports = to.lamports();
+
+    **campaign.try_borrow_mut_lamports()? = campaign_lamports
+        .checked_sub(amount)
+        .ok_or(ErrorCode::InsufficientFunds)?;
+    **to.try_borrow_mut_lamports()? = to_lamports
+        .checked_add(amount)
+        .ok_or(ErrorCode::ArithmeticOverflow)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitializeRegistry<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = Registry::LEN,
+        seeds = [b"registry"],
+        bump
+    )]
+    pub registry: Account<'info, Registry>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// The program's `ProgramData` account, holding its upgrade authority
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = anchor_lang::solana_program::bpf_loader_upgradeable::ID,
+        constraint = program_data.upgrade_authority_address == Some(admin.key())
+            @ ErrorCode::NotUpgradeAuthority
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateRegistry<'info> {
+    #[account(
+        mut,
+        seeds = [b"registry"],
+        bump = registry.bump,
+        has_one = admin @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub registry: Account<'info, Registry>,
+
+    pub admin: Signer<'info>,
+}
+
 #[derive(Accounts)]
-#[instruction(campaign_id: u64)]
 pub struct CreateCampaign<'info> {
+    #[account(mut, seeds = [b"registry"], bump = registry.bump)]
+    pub registry: Account<'info, Registry>,
+
     #[account(
         init,
         payer = creator,
         space = Campaign::LEN,
-        seeds = [b"campaign", creator.key().as_ref(), campaign_id.to_le_bytes().as_ref()],
+        seeds = [b"campaign", creator.key().as_ref(), registry.next_id.to_le_bytes().as_ref()],
         bump
     )]
     pub campaign_account: Account<'info, Campaign>,
 
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = CreatorStats::LEN,
+        seeds = [b"creator_stats", creator.key().as_ref()],
+        bump
+    )]
+    pub creator_stats: Account<'info, CreatorStats>,
+
+    /// Only for campaigns created with beneficiaries
+    #[account(
+        init,
+        payer = creator,
+        space = Beneficiaries::LEN,
+        seeds = [b"beneficiaries", campaign_account.key().as_ref()],
+        bump
+    )]
+    pub beneficiaries: Option<Account<'info, Beneficiaries>>,
+
     #[account(mut)]
     pub creator: Signer<'info>,
 
@@ -152,6 +537,15 @@ pub struct Donate<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct DonateBatch<'info> {
+    #[account(mut)]
+    pub donor: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    // Campaign PDAs are passed via remaining_accounts
+}
+
 #[derive(Accounts)]
 pub struct Withdraw<'info> {
     #[account(
@@ -159,8 +553,9 @@ pub struct Withdraw<'info> {
         seeds = [b"campaign", campaign_account.creator_pubkey.as_ref(), campaign_account.campaign_id.to_le_bytes().as_ref()],
         bump,
         // Keep business logic constraints here, move time checks to require! for clarity
-        constraint = campaign_account.amount_raised >= campaign_account.goal_amount @ ErrorCode::GoalNotReached,
-        constraint = !campaign_account.is_withdrawn @ ErrorCode::AlreadyWithdrawn
+        constraint = campaign_account.is_goal_reached() @ ErrorCode::GoalNotReached,
+        constraint = !campaign_account.is_withdrawn @ ErrorCode::AlreadyWithdrawn,
+        constraint = !campaign_account.has_beneficiaries @ ErrorCode::BeneficiariesRequireSplit
     )]
     pub campaign_account: Account<'info, Campaign>,
 
@@ -169,24 +564,238 @@ pub struct Withdraw<'info> {
         constraint = creator.key() == campaign_account.creator_pubkey @ ErrorCode::UnauthorizedWithdraw
     )]
     pub creator: Signer<'info>,
+
+    /// CHECK: Only receives lamports; the payout is authorized by the creator's signature.
+    /// Lets the funds go to a treasury or multisig instead of the signing wallet.
+    #[account(mut)]
+    pub destination: Option<UncheckedAccount<'info>>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawSplit<'info> {
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign_account.creator_pubkey.as_ref(), campaign_account.campaign_id.to_le_bytes().as_ref()],
+        bump,
+        constraint = campaign_account.is_goal_reached() @ ErrorCode::GoalNotReached,
+        constraint = !campaign_account.is_withdrawn @ ErrorCode::AlreadyWithdrawn
+    )]
+    pub campaign_account: Account<'info, Campaign>,
+
+    #[account(
+        seeds = [b"beneficiaries", campaign_account.key().as_ref()],
+        bump = beneficiaries.bump
+    )]
+    pub beneficiaries: Account<'info, Beneficiaries>,
+
+    #[account(
+        constraint = creator.key() == campaign_account.creator_pubkey @ ErrorCode::UnauthorizedWithdraw
+    )]
+    pub creator: Signer<'info>,
+    // Beneficiary accounts are passed via remaining_accounts
+}
+
+#[derive(Accounts)]
+pub struct CloseCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [b"campaign", campaign_account.creator_pubkey.as_ref(), campaign_account.campaign_id.to_le_bytes().as_ref()],
+        bump,
+        close = creator
+    )]
+    pub campaign_account: Account<'info, Campaign>,
+
+    #[account(
+        mut,
+        seeds = [b"creator_stats", creator.key().as_ref()],
+        bump = creator_stats.bump
+    )]
+    pub creator_stats: Account<'info, CreatorStats>,
+
+    /// Required for campaigns created with beneficiaries, and closed with them
+    #[account(
+        mut,
+        seeds = [b"beneficiaries", campaign_account.key().as_ref()],
+        bump = beneficiaries.bump,
+        close = creator
+    )]
+    pub beneficiaries: Option<Account<'info, Beneficiaries>>,
+
+    #[account(
+        mut,
+        constraint = creator.key() == campaign_account.creator_pubkey @ ErrorCode::UnauthorizedClose
+    )]
+    pub creator: Signer<'info>,
+}
+
+impl<'info> Withdraw<'info> {
+    /// The account credited by a withdrawal: `destination` if given, else the creator
+    fn payout_account(&self) -> AccountInfo<'info> {
+        match &self.destination {
+            Some(destination) => destination.to_account_info(),
+            None => self.creator.to_account_info(),
+        }
+    }
+}
+
+/// Global campaign id counter and platform settings, a PDA at `[b"registry"]`
+/// Campaign ids below `next_id` have all been assigned, so campaigns can be enumerated
+#[account]
+pub struct Registry {
+    pub next_id: u64,                   // 8 bytes (id the next campaign will get)
+    pub bump: u8,                       // 1 byte
+    pub admin: Pubkey,                  // 32 bytes (may change platform settings)
+    pub max_campaigns_per_creator: u32, // 4 bytes (0 = no cap)
+}
+
+impl Registry {
+    // Discriminator (8) + u64 (8) + u8 (1) + Pubkey (32) + u32 (4)
+    pub const LEN: usize = 8 + 8 + 1 + 32 + 4;
+}
+
+/// Per-creator bookkeeping, a PDA at `[b"creator_stats", creator]`
+#[account]
+pub struct CreatorStats {
+    pub active_campaign_count: u32, // 4 bytes (created and not yet closed)
+    pub bump: u8,                   // 1 byte
+}
+
+impl CreatorStats {
+    // Discriminator (8) + u32 (4) + u8 (1)
+    pub const LEN: usize = 8 + 4 + 1;
+}
+
+/// One payout recipient of a split campaign
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub struct BeneficiaryShare {
+    pub pubkey: Pubkey, // 32 bytes
+    pub share_bps: u16, // 2 bytes (basis points of each payout)
+}
+
+/// Fixed payout split of a campaign, a PDA at `[b"beneficiaries", campaign]`
+#[account]
+pub struct Beneficiaries {
+    pub campaign: Pubkey,              // 32 bytes
+    pub shares: Vec<BeneficiaryShare>, // 4 + 34 * MAX_BENEFICIARIES bytes
+    pub bump: u8,                      // 1 byte
+}
+
+impl Beneficiaries {
+    // Discriminator (8) + Pubkey (32) + Vec (4 + 34 * MAX_BENEFICIARIES) + u8 (1)
+    pub const LEN: usize = 8 + 32 + 4 + (32 + 2) * MAX_BENEFICIARIES + 1;
+
+    /// 1 to MAX_BENEFICIARIES distinct recipients with non-zero shares summing to 10000 bps
+    pub fn is_valid_split(shares: &[BeneficiaryShare]) -> bool {
+        if shares.is_empty() || shares.len() > MAX_BENEFICIARIES {
+            return false;
+        }
+        if shares.iter().any(|share| share.share_bps == 0) {
+            return false;
+        }
+        if shares
+            .iter()
+            .enumerate()
+            .any(|(i, share)| shares[..i].iter().any(|s| s.pubkey == share.pubkey))
+        {
+            return false;
+        }
+
+        shares
+            .iter()
+            .map(|share| share.share_bps as u32)
+            .sum::<u32>()
+            == TOTAL_SHARE_BPS as u32
+    }
 }
 
 #[account]
 pub struct Campaign {
-    pub creator_pubkey: Pubkey, // 32 bytes
-    pub campaign_id: u64,       // 8 bytes
-    pub goal_amount: u64,       // 8 bytes
-    pub amount_raised: u64,     // 8 bytes (historical total - may exceed goal)
-    pub deadline: i64,          // 8 bytes (i64 for timestamp)
-    pub metadata_url: String,   // 4 + MAX_METADATA_URL_LEN bytes
-    pub is_withdrawn: bool,     // 1 byte
-    pub withdrawn_amount: u64,  // 8 bytes (actual amount withdrawn)
+    pub creator_pubkey: Pubkey,      // 32 bytes
+    pub campaign_id: u64,            // 8 bytes
+    pub goal_amount: u64,            // 8 bytes
+    pub amount_raised: u64,          // 8 bytes (historical total - may exceed goal)
+    pub deadline: i64,               // 8 bytes (i64 for timestamp)
+    pub metadata_url: String,        // 4 + MAX_METADATA_URL_LEN bytes
+    pub is_withdrawn: bool,          // 1 byte
+    pub withdrawn_amount: u64,       // 8 bytes (actual amount withdrawn)
+    pub goal_reached_at: i64,        // 8 bytes (0 until the goal is first reached)
+    pub withdraw_cooldown_secs: u64, // 8 bytes (0 = no cooldown)
+    pub last_withdraw_at: i64,       // 8 bytes (0 until the first withdrawal)
+    pub decimals: u8,                // 1 byte (display precision of amounts, 9 for SOL)
+    pub symbol: [u8; 8],             // 8 bytes (ASCII, zero-padded; all zeros if unset)
+    pub has_beneficiaries: bool,     // 1 byte (payouts only via withdraw_split)
 }
 
 impl Campaign {
     pub const MAX_METADATA_URL_LEN: usize = 256;
-    // Discriminator (8) + Pubkey (32) + u64*4 (32) + i64 (8) + String (4 + 256) + bool (1)
-    pub const LEN: usize = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 4 + Self::MAX_METADATA_URL_LEN + 1;
+    pub const METADATA_URL_SCHEMES: [&'static str; 3] = ["https://", "http://", "ipfs://"];
+    // Discriminator (8) + Pubkey (32) + u64*5 (40) + i64*3 (24) + String (4 + 256) + bool (1)
+    // + u8 (1) + [u8; 8] (8) + bool (1)
+    pub const LEN: usize =
+        8 + 32 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 8 + 4 + Self::MAX_METADATA_URL_LEN + 1 + 1 + 8 + 1;
+
+    /// Smallest accepted goal: the account's rent-exempt minimum plus `MIN_GOAL_ABOVE_RENT`
+    pub fn min_goal_amount(rent: &Rent) -> u64 {
+        rent.minimum_balance(Self::LEN)
+            .saturating_add(MIN_GOAL_ABOVE_RENT)
+    }
+
+    /// Minimal scheme + host check so garbage never reaches the metadata resolver
+    /// Full URL parsing is left off-chain to keep compute cost low
+    pub fn is_valid_metadata_url(url: &str) -> bool {
+        if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+            return false;
+        }
+
+        Self::METADATA_URL_SCHEMES.iter().any(|scheme| {
+            url.strip_prefix(scheme)
+                .and_then(|rest| rest.split(['/', '?', '#']).next())
+                .is_some_and(|host| !host.is_empty())
+        })
+    }
+
+    /// Rejects a withdrawal made less than `withdraw_cooldown_secs` after the previous one
+    pub fn check_withdraw_cooldown(&self, now: i64) -> Result<()> {
+        if self.withdraw_cooldown_secs == 0 || self.last_withdraw_at == 0 {
+            return Ok(());
+        }
+
+        let cooldown = i64::try_from(self.withdraw_cooldown_secs).unwrap_or(i64::MAX);
+        require!(
+            now >= self.last_withdraw_at.saturating_add(cooldown),
+            ErrorCode::WithdrawCooldownActive
+        );
+        Ok(())
+    }
+
+    pub fn is_goal_reached(&self) -> bool {
+        self.amount_raised >= self.goal_amount
+    }
+
+    /// Share of the goal raised in basis points, capped at `FULL_PROGRESS_BPS`
+    /// once the goal is reached. Computed in u128 so large amounts can't overflow.
+    pub fn progress_bps(&self) -> u16 {
+        if self.is_goal_reached() {
+            return FULL_PROGRESS_BPS;
+        }
+        // Below the goal, so the quotient is under FULL_PROGRESS_BPS
+        (self.amount_raised as u128 * FULL_PROGRESS_BPS as u128 / self.goal_amount as u128) as u16
+    }
+
+    /// Adds a donation to `amount_raised`
+    /// Only the donation that crosses the goal records `goal_reached_at`
+    pub fn record_donation(&mut self, amount: u64, now: i64) -> Result<()> {
+        let was_goal_reached = self.is_goal_reached();
+        self.amount_raised = self
+            .amount_raised
+            .checked_add(amount)
+            .ok_or(ErrorCode::ArithmeticOverflow)?;
+
+        if !was_goal_reached && self.is_goal_reached() {
+            self.goal_reached_at = now;
+        }
+        Ok(())
+    }
 }
 
 #[event]
@@ -196,6 +805,8 @@ pub struct CampaignCreated {
     pub goal_amount: u64,
     pub deadline: i64,
     pub metadata_url: String,
+    pub decimals: u8,
+    pub symbol: [u8; 8],
 }
 
 #[event]
@@ -203,6 +814,10 @@ pub struct CampaignDonated {
     pub campaign_id: u64,
     pub donor_pubkey: Pubkey,
     pub amount: u64,
+    pub goal_reached_at: i64,
+    /// Campaign total including this donation
+    pub amount_raised: u64,
+    pub goal_amount: u64,
 }
 
 #[event]
@@ -210,6 +825,14 @@ pub struct CampaignWithdrawn {
     pub campaign_id: u64,
     pub creator_pubkey: Pubkey,
     pub amount: u64,
+    /// Account that received the funds
+    pub destination: Pubkey,
+}
+
+#[event]
+pub struct CampaignClosed {
+    pub campaign_id: u64,
+    pub creator_pubkey: Pubkey,
 }
 
 #[error_code]
@@ -238,4 +861,34 @@ pub enum ErrorCode {
     InsufficientFunds,
     #[msg("Campaign has already been withdrawn")]
     AlreadyWithdrawn,
+    #[msg("Batch must contain between 1 and MAX_BATCH_DONATIONS donations")]
+    InvalidBatchSize,
+    #[msg("Number of amounts does not match number of campaign accounts")]
+    BatchLengthMismatch,
+    #[msg("Account is not a writable SolRaiser campaign PDA")]
+    InvalidCampaignAccount,
+    #[msg("Metadata URL must be an http(s):// or ipfs:// URL with a host")]
+    InvalidMetadataUrl,
+    #[msg("Withdrawal cooldown has not elapsed since the last withdrawal")]
+    WithdrawCooldownActive,
+    #[msg("Creator has reached the maximum number of open campaigns")]
+    TooManyCampaigns,
+    #[msg("Only the platform admin can change platform settings")]
+    UnauthorizedAdmin,
+    #[msg("Unauthorized close - only campaign creator can close")]
+    UnauthorizedClose,
+    #[msg("Campaign still holds donated funds and cannot be closed")]
+    CampaignNotClosable,
+    #[msg(
+        "Beneficiary shares must be 1 to MAX_BENEFICIARIES distinct accounts summing to 10000 bps"
+    )]
+    InvalidBeneficiaryShares,
+    #[msg("Beneficiary accounts must match the registered beneficiaries, in order and writable")]
+    BeneficiaryAccountMismatch,
+    #[msg("Campaign pays out to its beneficiaries; use withdraw_split")]
+    BeneficiariesRequireSplit,
+    #[msg("Goal amount must exceed the campaign's rent-exempt minimum")]
+    GoalBelowMinimum,
+    #[msg("Only the program's upgrade authority can initialize the registry")]
+    NotUpgradeAuthority,
 }
@@ -1,5 +1,5 @@
 import * as anchor from "@coral-xyz/anchor";
-import { Program, BN } from "@coral-xyz/anchor";
+import { Program, BN, AnchorError } from "@coral-xyz/anchor";
 import { Solraiser } from "../target/types/solraiser";
 import { Keypair, PublicKey, LAMPORTS_PER_SOL } from "@solana/web3.js";
 import { assert } from "chai";
@@ -19,6 +19,10 @@ describe("solraiser", () => {
   // Constants
   const MIN_SOL_BALANCE = 5 * LAMPORTS_PER_SOL;
 
+  // Campaign symbols are fixed 8-byte, zero-padded ASCII
+  const symbolBytes = (symbol: string) => Array.from(Buffer.from(symbol.padEnd(8, "\0")));
+  const SOL_SYMBOL = symbolBytes("SOL");
+
   // Helper to process transactions
   async function confirmTransaction(tx: string) {
     const latestBlockHash = await provider.connection.getLatestBlockhash();
@@ -29,6 +33,18 @@ describe("solraiser", () => {
     });
   }
 
+  // Asserts that `tx` is rejected by the program with the error `code`
+  async function expectAnchorError(tx: Promise<unknown>, code: string) {
+    let error: unknown;
+    try {
+      await tx;
+    } catch (e) {
+      error = e;
+    }
+    assert.instanceOf(error, AnchorError, `Should have failed with ${code}`);
+    assert.strictEqual((error as AnchorError).error.errorCode.code, code);
+  }
+
   // Reliable airdrop helper
   async function airdrop(user: PublicKey, amount: number) {
     try {
@@ -55,10 +71,26 @@ describe("solraiser", () => {
       airdrop(donor.publicKey, MIN_SOL_BALANCE),
       airdrop(otherUser.publicKey, MIN_SOL_BALANCE),
     ]);
+
+    // The registry lives for the whole deployment, so it may exist from an earlier run.
+    // Only the upgrade authority may initialize it; `anchor test` deploys with the
+    // provider wallet as that authority, which then stays admin for later runs.
+    if (!(await program.account.registry.fetchNullable(registryPda))) {
+      await program.methods.initializeRegistry(0)
+        .accountsPartial({ registry: registryPda, admin: provider.wallet.publicKey })
+        .rpc();
+    }
   });
 
-  // Unique campaign ID generator to ensure tests don't collide on repeated runs
-  const generateCampaignId = () => new BN(Math.floor(Math.random() * 1_000_000_000));
+  const registryPda = PublicKey.findProgramAddressSync(
+    [Buffer.from("registry")],
+    program.programId
+  )[0];
+
+  // The id the registry will assign to the next created campaign
+  async function nextCampaignId() {
+    return (await program.account.registry.fetch(registryPda)).nextId;
+  }
 
   // Helper to derive PDA
   function getCampaignAddress(creatorKey: PublicKey, campaignsId: BN) {
@@ -72,8 +104,34 @@ describe("solraiser", () => {
     )[0];
   }
 
+  // Creates a campaign, donates `donation` to it and waits until the deadline has passed
+  async function createFundedExpiredCampaign(goalAmount: BN, donation: BN, metadataUrl: string, withdrawCooldownSecs = new BN(0)) {
+    const campaignId = await nextCampaignId();
+    const deadline = new BN(Math.floor(Date.now() / 1000) + 2);
+    const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
+
+    await program.methods.createCampaign(goalAmount, deadline, metadataUrl, withdrawCooldownSecs, 9, SOL_SYMBOL, [])
+      .accountsPartial({
+        creator: creator.publicKey,
+        campaignAccount: campaignPda,
+      })
+      .signers([creator])
+      .rpc();
+
+    await program.methods.donate(donation)
+      .accountsPartial({
+        campaignAccount: campaignPda,
+        donor: donor.publicKey,
+      })
+      .signers([donor])
+      .rpc();
+
+    await new Promise(r => setTimeout(r, 4000));
+    return campaignPda;
+  }
+
   it("Creates a campaign successfully", async () => {
-    const campaignId = generateCampaignId();
+    const campaignId = await nextCampaignId();
     const goalAmount = new BN(1 * LAMPORTS_PER_SOL);
     const deadline = new BN(Math.floor(Date.now() / 1000) + 60); // 1 minute from now
     const metadataUrl = "https://example.com/project";
@@ -81,7 +139,7 @@ describe("solraiser", () => {
     const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
 
     await program.methods
-      .createCampaign(campaignId, goalAmount, deadline, metadataUrl)
+      .createCampaign(goalAmount, deadline, metadataUrl, new BN(0), 9, SOL_SYMBOL, [])
       .accountsPartial({
         creator: creator.publicKey,
         campaignAccount: campaignPda,
@@ -101,8 +159,32 @@ describe("solraiser", () => {
     assert.ok(campaignAccount.withdrawnAmount.eq(new BN(0)));
   });
 
+  it("Assigns sequential ids from the registry", async () => {
+    const deadline = new BN(Math.floor(Date.now() / 1000) + 60);
+    const firstId = await nextCampaignId();
+    const ids: BN[] = [];
+
+    for (const metadataUrl of ["https://example.com/registry_a", "https://example.com/registry_b"]) {
+      const campaignId = await nextCampaignId();
+      await program.methods.createCampaign(new BN(LAMPORTS_PER_SOL), deadline, metadataUrl, new BN(0), 9, SOL_SYMBOL, [])
+        .accountsPartial({
+          creator: creator.publicKey,
+          campaignAccount: getCampaignAddress(creator.publicKey, campaignId),
+        })
+        .signers([creator])
+        .rpc();
+
+      const campaignAccount = await program.account.campaign.fetch(getCampaignAddress(creator.publicKey, campaignId));
+      ids.push(campaignAccount.campaignId);
+    }
+
+    assert.ok(ids[0].eq(firstId));
+    assert.ok(ids[1].eq(firstId.add(new BN(1))));
+    assert.ok((await nextCampaignId()).eq(firstId.add(new BN(2))));
+  });
+
   it("Donates to a campaign successfully", async () => {
-    const campaignId = generateCampaignId();
+    const campaignId = await nextCampaignId();
     const goalAmount = new BN(5 * LAMPORTS_PER_SOL);
     const deadline = new BN(Math.floor(Date.now() / 1000) + 60);
     const metadataUrl = "https://example.com/donate";
@@ -110,7 +192,7 @@ describe("solraiser", () => {
     // 1. Create
     const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
     await program.methods
-      .createCampaign(campaignId, goalAmount, deadline, metadataUrl)
+      .createCampaign(goalAmount, deadline, metadataUrl, new BN(0), 9, SOL_SYMBOL, [])
       .accountsPartial({
         creator: creator.publicKey,
         campaignAccount: campaignPda,
@@ -138,8 +220,46 @@ describe("solraiser", () => {
     // but relying on program state is usually sufficient for checking logic correctness.
   });
 
+  it("Counts an anonymous donation without naming the donor in the event", async () => {
+    const campaignId = await nextCampaignId();
+    const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
+    await program.methods
+      .createCampaign(new BN(5 * LAMPORTS_PER_SOL), new BN(Math.floor(Date.now() / 1000) + 60), "https://example.com/anonymous", new BN(0), 9, SOL_SYMBOL, [])
+      .accountsPartial({
+        creator: creator.publicKey,
+        campaignAccount: campaignPda,
+      })
+      .signers([creator])
+      .rpc();
+
+    const donationAmount = new BN(1 * LAMPORTS_PER_SOL);
+    const signature = await program.methods
+      .donateAnonymous(donationAmount)
+      .accountsPartial({
+        campaignAccount: campaignPda,
+        donor: donor.publicKey,
+      })
+      .signers([donor])
+      .rpc({ commitment: "confirmed" });
+
+    const campaignAccount = await program.account.campaign.fetch(campaignPda);
+    assert.ok(campaignAccount.amountRaised.eq(donationAmount));
+
+    const tx = await provider.connection.getTransaction(signature, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    const parser = new anchor.EventParser(program.programId, new anchor.BorshCoder(program.idl));
+    const events = Array.from(parser.parseLogs(tx.meta.logMessages));
+    const donated = events.find((e) => e.name === "campaignDonated");
+    assert.ok(donated.data.donorPubkey.equals(PublicKey.default));
+    assert.ok(donated.data.amount.eq(donationAmount));
+    assert.ok(donated.data.amountRaised.eq(campaignAccount.amountRaised));
+    assert.ok(donated.data.goalAmount.eq(campaignAccount.goalAmount));
+  });
+
   it("Fails to withdraw if goal not met", async () => {
-    const campaignId = generateCampaignId();
+    const campaignId = await nextCampaignId();
     const goalAmount = new BN(10 * LAMPORTS_PER_SOL);
     // Deadline in past for withdrawal check? 
     // Wait, withdrawal requires (goal reached) AND (deadline passed).
@@ -158,7 +278,7 @@ describe("solraiser", () => {
     const validDeadline = new BN(Math.floor(Date.now() / 1000) + 2); // 2 seconds
     
     await program.methods
-      .createCampaign(campaignId, goalAmount, validDeadline, "fail_withdraw")
+      .createCampaign(goalAmount, validDeadline, "https://example.com/fail_withdraw", new BN(0), 9, SOL_SYMBOL, [])
       .accountsPartial({
         creator: creator.publicKey,
         campaignAccount: campaignPda,
@@ -193,14 +313,14 @@ describe("solraiser", () => {
   });
 
   it("Allows withdrawal if goal reached and deadline passed", async () => {
-    const campaignId = generateCampaignId();
+    const campaignId = await nextCampaignId();
     const goalAmount = new BN(1 * LAMPORTS_PER_SOL); // Small goal
     const deadline = new BN(Math.floor(Date.now() / 1000) + 2); // 2 seconds
 
     const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
 
     // 1. Create
-    await program.methods.createCampaign(campaignId, goalAmount, deadline, "withdraw_ok")
+    await program.methods.createCampaign(goalAmount, deadline, "https://example.com/withdraw_ok", new BN(0), 9, SOL_SYMBOL, [])
       .accountsPartial({
         creator: creator.publicKey,
         campaignAccount: campaignPda,
@@ -244,21 +364,21 @@ describe("solraiser", () => {
     assert.ok(creatorAfter > creatorBefore); 
     
     // Campaign PDA should only have rent-exempt minimum left
-    // Campaign::LEN = 8 + 32 + 8 + 8 + 8 + 8 + 8 + 4 + 256 + 1 = 341 bytes
-    const CAMPAIGN_SIZE = 341;
+    // Campaign::LEN = 8 + 32 + 8 * 8 + 4 + 256 + 1 + 1 + 8 + 1 = 375 bytes
+    const CAMPAIGN_SIZE = 375;
     const rent = await provider.connection.getMinimumBalanceForRentExemption(CAMPAIGN_SIZE);
     assert.ok(campaignBalanceAfter >= rent && campaignBalanceAfter <= rent + 5000); // Allow small variance
   });
 
   it("Allows overfunding (donations beyond goal)", async () => {
-    const campaignId = generateCampaignId();
+    const campaignId = await nextCampaignId();
     const goalAmount = new BN(1 * LAMPORTS_PER_SOL);
     const deadline = new BN(Math.floor(Date.now() / 1000) + 3);
 
     const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
 
     // Create campaign
-    await program.methods.createCampaign(campaignId, goalAmount, deadline, "overfund_test")
+    await program.methods.createCampaign(goalAmount, deadline, "https://example.com/overfund_test", new BN(0), 9, SOL_SYMBOL, [])
       .accountsPartial({
         creator: creator.publicKey,
         campaignAccount: campaignPda,
@@ -313,4 +433,618 @@ describe("solraiser", () => {
     assert.ok(finalCampaign.withdrawnAmount.gt(goalAmount)); // Should have withdrawn MORE than goal
     assert.ok(creatorAfter > creatorBefore);
   });
+
+  it("Records goal_reached_at only on the donation that crosses the goal", async () => {
+    const campaignId = await nextCampaignId();
+    const goalAmount = new BN(1 * LAMPORTS_PER_SOL);
+    const deadline = new BN(Math.floor(Date.now() / 1000) + 60);
+
+    const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
+
+    await program.methods.createCampaign(goalAmount, deadline, "https://example.com/goal_reached_at", new BN(0), 9, SOL_SYMBOL, [])
+      .accountsPartial({
+        creator: creator.publicKey,
+        campaignAccount: campaignPda,
+      })
+      .signers([creator])
+      .rpc();
+
+    // Donate to just below the goal
+    await program.methods.donate(goalAmount.subn(1))
+      .accountsPartial({
+        campaignAccount: campaignPda,
+        donor: donor.publicKey,
+      })
+      .signers([donor])
+      .rpc();
+
+    let campaignAccount = await program.account.campaign.fetch(campaignPda);
+    assert.ok(campaignAccount.goalReachedAt.eq(new BN(0)));
+
+    // Cross the goal
+    await program.methods.donate(new BN(1))
+      .accountsPartial({
+        campaignAccount: campaignPda,
+        donor: donor.publicKey,
+      })
+      .signers([donor])
+      .rpc();
+
+    campaignAccount = await program.account.campaign.fetch(campaignPda);
+    const goalReachedAt = campaignAccount.goalReachedAt;
+    assert.ok(goalReachedAt.gt(new BN(0)));
+
+    // Let the clock advance, then overfund - the timestamp must not move
+    await new Promise(r => setTimeout(r, 2000));
+    await program.methods.donate(new BN(0.1 * LAMPORTS_PER_SOL))
+      .accountsPartial({
+        campaignAccount: campaignPda,
+        donor: donor.publicKey,
+      })
+      .signers([donor])
+      .rpc();
+
+    campaignAccount = await program.account.campaign.fetch(campaignPda);
+    assert.ok(campaignAccount.goalReachedAt.eq(goalReachedAt));
+  });
+
+  it("Withdraws a partial amount and keeps the rest on-chain", async () => {
+    const goalAmount = new BN(1 * LAMPORTS_PER_SOL);
+    const campaignPda = await createFundedExpiredCampaign(goalAmount, goalAmount, "https://example.com/partial_ok");
+
+    const drawAmount = new BN(0.25 * LAMPORTS_PER_SOL);
+    const campaignBefore = await provider.connection.getBalance(campaignPda);
+
+    await program.methods.withdrawPartial(drawAmount)
+      .accountsPartial({
+        campaignAccount: campaignPda,
+        creator: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    const campaignAfter = await provider.connection.getBalance(campaignPda);
+    const campaignAccount = await program.account.campaign.fetch(campaignPda);
+
+    assert.strictEqual(campaignBefore - campaignAfter, drawAmount.toNumber());
+    assert.ok(campaignAccount.withdrawnAmount.eq(drawAmount));
+    assert.strictEqual(campaignAccount.isWithdrawn, false);
+  });
+
+  it("Rejects a partial withdrawal that would dip below rent", async () => {
+    const goalAmount = new BN(1 * LAMPORTS_PER_SOL);
+    const campaignPda = await createFundedExpiredCampaign(goalAmount, goalAmount, "https://example.com/partial_over");
+
+    const campaignBalance = await provider.connection.getBalance(campaignPda);
+
+    await expectAnchorError(
+      program.methods.withdrawPartial(new BN(campaignBalance))
+        .accountsPartial({
+          campaignAccount: campaignPda,
+          creator: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc(),
+      "InsufficientFunds"
+    );
+  });
+
+  it("Accumulates sequential partial withdrawals", async () => {
+    const goalAmount = new BN(1 * LAMPORTS_PER_SOL);
+    const campaignPda = await createFundedExpiredCampaign(goalAmount, goalAmount, "https://example.com/partial_twice");
+
+    const firstDraw = new BN(0.3 * LAMPORTS_PER_SOL);
+    const secondDraw = new BN(0.2 * LAMPORTS_PER_SOL);
+
+    for (const draw of [firstDraw, secondDraw]) {
+      await program.methods.withdrawPartial(draw)
+        .accountsPartial({
+          campaignAccount: campaignPda,
+          creator: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+    }
+
+    const campaignAccount = await program.account.campaign.fetch(campaignPda);
+    assert.ok(campaignAccount.withdrawnAmount.eq(firstDraw.add(secondDraw)));
+    assert.strictEqual(campaignAccount.isWithdrawn, false);
+  });
+
+  it("Donates to multiple campaigns in one batch", async () => {
+    const goalAmount = new BN(5 * LAMPORTS_PER_SOL);
+    const deadline = new BN(Math.floor(Date.now() / 1000) + 60);
+    const campaignPdas: PublicKey[] = [];
+
+    for (const metadataUrl of ["https://example.com/batch_a", "https://example.com/batch_b"]) {
+      const campaignId = await nextCampaignId();
+      const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
+      await program.methods.createCampaign(goalAmount, deadline, metadataUrl, new BN(0), 9, SOL_SYMBOL, [])
+        .accountsPartial({
+          creator: creator.publicKey,
+          campaignAccount: campaignPda,
+        })
+        .signers([creator])
+        .rpc();
+      campaignPdas.push(campaignPda);
+    }
+
+    const amounts = [new BN(0.2 * LAMPORTS_PER_SOL), new BN(0.3 * LAMPORTS_PER_SOL)];
+    await program.methods.donateBatch(amounts)
+      .accountsPartial({
+        donor: donor.publicKey,
+      })
+      .remainingAccounts(campaignPdas.map(pubkey => ({ pubkey, isWritable: true, isSigner: false })))
+      .signers([donor])
+      .rpc();
+
+    for (let i = 0; i < campaignPdas.length; i++) {
+      const campaignAccount = await program.account.campaign.fetch(campaignPdas[i]);
+      assert.ok(campaignAccount.amountRaised.eq(amounts[i]));
+    }
+  });
+
+  it("Fails the whole batch if one campaign has expired", async () => {
+    const goalAmount = new BN(5 * LAMPORTS_PER_SOL);
+
+    const activeId = await nextCampaignId();
+    const activePda = getCampaignAddress(creator.publicKey, activeId);
+    await program.methods.createCampaign(goalAmount, new BN(Math.floor(Date.now() / 1000) + 60), "https://example.com/batch_active", new BN(0), 9, SOL_SYMBOL, [])
+      .accountsPartial({
+        creator: creator.publicKey,
+        campaignAccount: activePda,
+      })
+      .signers([creator])
+      .rpc();
+
+    const expiredId = await nextCampaignId();
+    const expiredPda = getCampaignAddress(creator.publicKey, expiredId);
+    await program.methods.createCampaign(goalAmount, new BN(Math.floor(Date.now() / 1000) + 2), "https://example.com/batch_expired", new BN(0), 9, SOL_SYMBOL, [])
+      .accountsPartial({
+        creator: creator.publicKey,
+        campaignAccount: expiredPda,
+      })
+      .signers([creator])
+      .rpc();
+
+    await new Promise(r => setTimeout(r, 4000));
+
+    await expectAnchorError(
+      program.methods.donateBatch([new BN(0.1 * LAMPORTS_PER_SOL), new BN(0.1 * LAMPORTS_PER_SOL)])
+        .accountsPartial({
+          donor: donor.publicKey,
+        })
+        .remainingAccounts([activePda, expiredPda].map(pubkey => ({ pubkey, isWritable: true, isSigner: false })))
+        .signers([donor])
+        .rpc(),
+      "CampaignExpired"
+    );
+
+    // The active campaign's donation was rolled back with the rest of the batch
+    const activeAccount = await program.account.campaign.fetch(activePda);
+    assert.ok(activeAccount.amountRaised.eq(new BN(0)));
+  });
+
+  it("Accepts https and ipfs metadata URLs", async () => {
+    const goalAmount = new BN(1 * LAMPORTS_PER_SOL);
+    const deadline = new BN(Math.floor(Date.now() / 1000) + 60);
+
+    for (const metadataUrl of [
+      "https://example.com/campaign.json",
+      "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi",
+    ]) {
+      const campaignId = await nextCampaignId();
+      const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
+
+      await program.methods.createCampaign(goalAmount, deadline, metadataUrl, new BN(0), 9, SOL_SYMBOL, [])
+        .accountsPartial({
+          creator: creator.publicKey,
+          campaignAccount: campaignPda,
+        })
+        .signers([creator])
+        .rpc();
+
+      const campaignAccount = await program.account.campaign.fetch(campaignPda);
+      assert.strictEqual(campaignAccount.metadataUrl, metadataUrl);
+    }
+  });
+
+  it("Rejects a metadata URL that is not a URL", async () => {
+    const campaignId = await nextCampaignId();
+    const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
+
+    await expectAnchorError(
+      program.methods
+        .createCampaign(new BN(1 * LAMPORTS_PER_SOL), new BN(Math.floor(Date.now() / 1000) + 60), "my campaign", new BN(0), 9, SOL_SYMBOL, [])
+        .accountsPartial({
+          creator: creator.publicKey,
+          campaignAccount: campaignPda,
+        })
+        .signers([creator])
+        .rpc(),
+      "InvalidMetadataUrl"
+    );
+  });
+
+  describe("minimum goal", () => {
+    // Must match MIN_GOAL_ABOVE_RENT in the program
+    const MIN_GOAL_ABOVE_RENT = 1_000_000;
+    const CAMPAIGN_SIZE = 375;
+
+    async function minGoal() {
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(CAMPAIGN_SIZE);
+      return new BN(rent + MIN_GOAL_ABOVE_RENT);
+    }
+
+    it("Rejects a goal below the rent-exempt minimum plus the floor", async () => {
+      const goalAmount = (await minGoal()).sub(new BN(1));
+
+      for (const goal of [new BN(1), goalAmount]) {
+        const campaignId = await nextCampaignId();
+        const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
+        await expectAnchorError(
+          program.methods
+            .createCampaign(goal, new BN(Math.floor(Date.now() / 1000) + 60), "https://example.com/tiny_goal", new BN(0), 9, SOL_SYMBOL, [])
+            .accountsPartial({
+              creator: creator.publicKey,
+              campaignAccount: campaignPda,
+            })
+            .signers([creator])
+            .rpc(),
+          "GoalBelowMinimum"
+        );
+      }
+    });
+
+    it("Accepts the minimum goal and leaves the PDA rent-exempt after withdrawal", async () => {
+      const goalAmount = await minGoal();
+      const campaignId = await nextCampaignId();
+      const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
+      const deadline = new BN(Math.floor(Date.now() / 1000) + 2);
+
+      await program.methods.createCampaign(goalAmount, deadline, "https://example.com/min_goal", new BN(0), 9, SOL_SYMBOL, [])
+        .accountsPartial({
+          creator: creator.publicKey,
+          campaignAccount: campaignPda,
+        })
+        .signers([creator])
+        .rpc();
+
+      await program.methods.donate(goalAmount)
+        .accountsPartial({
+          campaignAccount: campaignPda,
+          donor: donor.publicKey,
+        })
+        .signers([donor])
+        .rpc();
+
+      await new Promise(r => setTimeout(r, 6000));
+
+      await program.methods.withdraw()
+        .accountsPartial({
+          campaignAccount: campaignPda,
+          creator: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+
+      const campaignAccount = await program.account.campaign.fetch(campaignPda);
+      assert.ok(campaignAccount.withdrawnAmount.gte(goalAmount));
+
+      const rent = await provider.connection.getMinimumBalanceForRentExemption(CAMPAIGN_SIZE);
+      const campaignBalance = await provider.connection.getBalance(campaignPda);
+      assert.ok(campaignBalance >= rent);
+    });
+  });
+
+  it("Withdraws to the creator when no destination is given", async () => {
+    const goalAmount = new BN(1 * LAMPORTS_PER_SOL);
+    const campaignPda = await createFundedExpiredCampaign(goalAmount, goalAmount, "https://example.com/withdraw_default");
+
+    const creatorBefore = await provider.connection.getBalance(creator.publicKey);
+
+    await program.methods.withdraw()
+      .accountsPartial({
+        campaignAccount: campaignPda,
+        creator: creator.publicKey,
+        destination: null,
+      })
+      .signers([creator])
+      .rpc();
+
+    const creatorAfter = await provider.connection.getBalance(creator.publicKey);
+    const campaignAccount = await program.account.campaign.fetch(campaignPda);
+
+    assert.ok(campaignAccount.isWithdrawn === true);
+    assert.ok(creatorAfter > creatorBefore);
+  });
+
+  it("Withdraws to an explicit destination while the creator signs", async () => {
+    const goalAmount = new BN(1 * LAMPORTS_PER_SOL);
+    const campaignPda = await createFundedExpiredCampaign(goalAmount, goalAmount, "https://example.com/withdraw_treasury");
+    const treasury = Keypair.generate();
+
+    const creatorBefore = await provider.connection.getBalance(creator.publicKey);
+
+    await program.methods.withdraw()
+      .accountsPartial({
+        campaignAccount: campaignPda,
+        creator: creator.publicKey,
+        destination: treasury.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    const creatorAfter = await provider.connection.getBalance(creator.publicKey);
+    const treasuryBalance = await provider.connection.getBalance(treasury.publicKey);
+    const campaignAccount = await program.account.campaign.fetch(campaignPda);
+
+    // The treasury receives the payout; the creator only pays the fee
+    assert.ok(campaignAccount.isWithdrawn === true);
+    assert.strictEqual(treasuryBalance, campaignAccount.withdrawnAmount.toNumber());
+    assert.ok(creatorAfter <= creatorBefore);
+  });
+
+  it("Rejects a second withdrawal inside the cooldown", async () => {
+    const goalAmount = new BN(1 * LAMPORTS_PER_SOL);
+    const campaignPda = await createFundedExpiredCampaign(goalAmount, goalAmount, "https://example.com/cooldown_active", new BN(3600));
+    const draw = new BN(0.1 * LAMPORTS_PER_SOL);
+
+    await program.methods.withdrawPartial(draw)
+      .accountsPartial({
+        campaignAccount: campaignPda,
+        creator: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    await expectAnchorError(
+      program.methods.withdrawPartial(draw)
+        .accountsPartial({
+          campaignAccount: campaignPda,
+          creator: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc(),
+      "WithdrawCooldownActive"
+    );
+
+    const campaignAccount = await program.account.campaign.fetch(campaignPda);
+    assert.ok(campaignAccount.withdrawnAmount.eq(draw));
+  });
+
+  it("Allows a withdrawal once the cooldown has elapsed", async () => {
+    const goalAmount = new BN(1 * LAMPORTS_PER_SOL);
+    const campaignPda = await createFundedExpiredCampaign(goalAmount, goalAmount, "https://example.com/cooldown_elapsed", new BN(2));
+    const draw = new BN(0.1 * LAMPORTS_PER_SOL);
+
+    await program.methods.withdrawPartial(draw)
+      .accountsPartial({
+        campaignAccount: campaignPda,
+        creator: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+    const firstWithdrawAt = (await program.account.campaign.fetch(campaignPda)).lastWithdrawAt;
+
+    await new Promise(r => setTimeout(r, 4000));
+
+    await program.methods.withdrawPartial(draw)
+      .accountsPartial({
+        campaignAccount: campaignPda,
+        creator: creator.publicKey,
+      })
+      .signers([creator])
+      .rpc();
+
+    const campaignAccount = await program.account.campaign.fetch(campaignPda);
+    assert.ok(campaignAccount.withdrawnAmount.eq(draw.add(draw)));
+    assert.ok(campaignAccount.lastWithdrawAt.gt(firstWithdrawAt));
+  });
+
+  it("Stores display decimals and symbol set at creation", async () => {
+    const campaignId = await nextCampaignId();
+    const campaignPda = getCampaignAddress(creator.publicKey, campaignId);
+    const usdcSymbol = symbolBytes("USDC");
+
+    await program.methods
+      .createCampaign(new BN(5_000_000), new BN(Math.floor(Date.now() / 1000) + 60), "https://example.com/usdc", new BN(0), 6, usdcSymbol, [])
+      .accountsPartial({
+        creator: creator.publicKey,
+        campaignAccount: campaignPda,
+      })
+      .signers([creator])
+      .rpc();
+
+    const campaignAccount = await program.account.campaign.fetch(campaignPda);
+    assert.strictEqual(campaignAccount.decimals, 6);
+    assert.deepStrictEqual(Array.from(campaignAccount.symbol), usdcSymbol);
+    assert.strictEqual(Buffer.from(campaignAccount.symbol).toString().replace(/\0+$/, ""), "USDC");
+  });
+
+  describe("per-creator campaign cap", () => {
+    let cappedCreator: Keypair;
+
+    // Opens a campaign for `cappedCreator` and returns its PDA
+    async function createCappedCampaign(metadataUrl: string) {
+      const campaignPda = getCampaignAddress(cappedCreator.publicKey, await nextCampaignId());
+      await program.methods.createCampaign(new BN(LAMPORTS_PER_SOL), new BN(Math.floor(Date.now() / 1000) + 60), metadataUrl, new BN(0), 9, SOL_SYMBOL, [])
+        .accountsPartial({
+          creator: cappedCreator.publicKey,
+          campaignAccount: campaignPda,
+        })
+        .signers([cappedCreator])
+        .rpc();
+      return campaignPda;
+    }
+
+    before(async () => {
+      cappedCreator = Keypair.generate();
+      await airdrop(cappedCreator.publicKey, MIN_SOL_BALANCE);
+      await program.methods.setMaxCampaignsPerCreator(2)
+        .accountsPartial({ registry: registryPda, admin: provider.wallet.publicKey })
+        .rpc();
+    });
+
+    after(async () => {
+      await program.methods.setMaxCampaignsPerCreator(0)
+        .accountsPartial({ registry: registryPda, admin: provider.wallet.publicKey })
+        .rpc();
+    });
+
+    it("Rejects a campaign beyond the cap and frees a slot on close", async () => {
+      const first = await createCappedCampaign("https://example.com/cap_a");
+      await createCappedCampaign("https://example.com/cap_b");
+
+      await expectAnchorError(
+        createCappedCampaign("https://example.com/cap_c"),
+        "TooManyCampaigns"
+      );
+
+      await program.methods.closeCampaign()
+        .accountsPartial({
+          campaignAccount: first,
+          creator: cappedCreator.publicKey,
+        })
+        .signers([cappedCreator])
+        .rpc();
+      assert.isNull(await program.account.campaign.fetchNullable(first));
+
+      await createCappedCampaign("https://example.com/cap_c");
+    });
+
+    it("Rejects closing a campaign that holds donations", async () => {
+      await program.methods.setMaxCampaignsPerCreator(0)
+        .accountsPartial({ registry: registryPda, admin: provider.wallet.publicKey })
+        .rpc();
+      const campaignPda = await createCappedCampaign("https://example.com/cap_funded");
+      await program.methods.donate(new BN(0.1 * LAMPORTS_PER_SOL))
+        .accountsPartial({
+          campaignAccount: campaignPda,
+          donor: donor.publicKey,
+        })
+        .signers([donor])
+        .rpc();
+
+      await expectAnchorError(
+        program.methods.closeCampaign()
+          .accountsPartial({
+            campaignAccount: campaignPda,
+            creator: cappedCreator.publicKey,
+          })
+          .signers([cappedCreator])
+          .rpc(),
+        "CampaignNotClosable"
+      );
+    });
+
+    it("Only lets the admin change the cap", async () => {
+      await expectAnchorError(
+        program.methods.setMaxCampaignsPerCreator(1)
+          .accountsPartial({ registry: registryPda, admin: otherUser.publicKey })
+          .signers([otherUser])
+          .rpc(),
+        "UnauthorizedAdmin"
+      );
+    });
+  });
+
+  describe("beneficiary split", () => {
+    function getBeneficiariesAddress(campaignPda: PublicKey) {
+      return PublicKey.findProgramAddressSync(
+        [Buffer.from("beneficiaries"), campaignPda.toBuffer()],
+        program.programId
+      )[0];
+    }
+
+    // Creates a campaign that expires in 2 seconds, split between `shares`, and returns its PDA
+    async function createSplitCampaign(metadataUrl: string, shares: { pubkey: PublicKey; shareBps: number }[]) {
+      const campaignPda = getCampaignAddress(creator.publicKey, await nextCampaignId());
+      await program.methods.createCampaign(new BN(LAMPORTS_PER_SOL), new BN(Math.floor(Date.now() / 1000) + 2), metadataUrl, new BN(0), 9, SOL_SYMBOL, shares)
+        .accountsPartial({
+          creator: creator.publicKey,
+          campaignAccount: campaignPda,
+          beneficiaries: getBeneficiariesAddress(campaignPda),
+        })
+        .signers([creator])
+        .rpc();
+      return campaignPda;
+    }
+
+    it("Splits a withdrawal 60/40 between beneficiaries", async () => {
+      const first = Keypair.generate();
+      const second = Keypair.generate();
+      const campaignPda = await createSplitCampaign("https://example.com/split_60_40", [
+        { pubkey: first.publicKey, shareBps: 6000 },
+        { pubkey: second.publicKey, shareBps: 4000 },
+      ]);
+
+      await program.methods.donate(new BN(LAMPORTS_PER_SOL))
+        .accountsPartial({
+          campaignAccount: campaignPda,
+          donor: donor.publicKey,
+        })
+        .signers([donor])
+        .rpc();
+
+      await new Promise(r => setTimeout(r, 4000));
+
+      // A split campaign can't be paid out to a single account
+      await expectAnchorError(
+        program.methods.withdraw()
+          .accountsPartial({
+            campaignAccount: campaignPda,
+            creator: creator.publicKey,
+          })
+          .signers([creator])
+          .rpc(),
+        "BeneficiariesRequireSplit"
+      );
+
+      await program.methods.withdrawSplit()
+        .accountsPartial({
+          campaignAccount: campaignPda,
+          beneficiaries: getBeneficiariesAddress(campaignPda),
+          creator: creator.publicKey,
+        })
+        .remainingAccounts([
+          { pubkey: first.publicKey, isWritable: true, isSigner: false },
+          { pubkey: second.publicKey, isWritable: true, isSigner: false },
+        ])
+        .signers([creator])
+        .rpc();
+
+      const campaignAccount = await program.account.campaign.fetch(campaignPda);
+      const withdrawn = campaignAccount.withdrawnAmount.toNumber();
+      const firstBalance = await provider.connection.getBalance(first.publicKey);
+      const secondBalance = await provider.connection.getBalance(second.publicKey);
+
+      assert.ok(campaignAccount.isWithdrawn === true);
+      assert.strictEqual(withdrawn, LAMPORTS_PER_SOL);
+      assert.strictEqual(firstBalance, Math.floor(withdrawn * 6000 / 10000));
+      assert.strictEqual(firstBalance + secondBalance, withdrawn);
+
+      // Closing the campaign closes its beneficiaries too
+      await program.methods.closeCampaign()
+        .accountsPartial({
+          campaignAccount: campaignPda,
+          beneficiaries: getBeneficiariesAddress(campaignPda),
+          creator: creator.publicKey,
+        })
+        .signers([creator])
+        .rpc();
+      assert.isNull(await program.account.campaign.fetchNullable(campaignPda));
+      assert.isNull(await program.account.beneficiaries.fetchNullable(getBeneficiariesAddress(campaignPda)));
+    });
+
+    it("Rejects shares that don't sum to 10000 bps", async () => {
+      await expectAnchorError(
+        createSplitCampaign("https://example.com/split_invalid", [
+          { pubkey: Keypair.generate().publicKey, shareBps: 6000 },
+          { pubkey: Keypair.generate().publicKey, shareBps: 3000 },
+        ]),
+        "InvalidBeneficiaryShares"
+      );
+    });
+  });
 });